@@ -0,0 +1,75 @@
+//! Benchmarks of the MinMax search and hot board operations.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use viergewinnt_rs::{SearchConfig, midgame_positions, search_with_config};
+
+fn bench_minmax_search(c: &mut Criterion) {
+    let mut group = c.benchmark_group("minmax_search");
+    group.sample_size(10);
+    for (i, game) in midgame_positions().into_iter().enumerate() {
+        group.bench_function(format!("midgame_{i}"), |b| {
+            b.iter(|| {
+                search_with_config(
+                    black_box(game.board().clone()),
+                    game.turn(),
+                    &SearchConfig::default(),
+                )
+            });
+        });
+    }
+    group.finish();
+}
+
+/// Compares plain and evaluated leaves at a depth where both are feasible.
+fn bench_evaluated_leaves(c: &mut Criterion) {
+    let mut group = c.benchmark_group("evaluated_leaves");
+    group.sample_size(10);
+    for (i, game) in midgame_positions().into_iter().enumerate() {
+        for evaluate_leaves in [false, true] {
+            let config = SearchConfig {
+                max_depth: 7,
+                evaluate_leaves,
+                ..SearchConfig::default()
+            };
+            let name = if evaluate_leaves {
+                "evaluated"
+            } else {
+                "plain"
+            };
+            group.bench_function(format!("{name}_depth_7/midgame_{i}"), |b| {
+                b.iter(|| {
+                    search_with_config(black_box(game.board().clone()), game.turn(), &config)
+                });
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_board(c: &mut Criterion) {
+    let games = midgame_positions();
+    c.bench_function("available_columns_iter", |b| {
+        b.iter(|| {
+            games
+                .iter()
+                .map(|game| black_box(game.board()).available_columns_iter().count())
+                .sum::<usize>()
+        });
+    });
+    c.bench_function("check_for_winner", |b| {
+        b.iter(|| {
+            games
+                .iter()
+                .filter(|game| black_box(game.board()).check_for_winner(game.turn()))
+                .count()
+        });
+    });
+}
+
+criterion_group!(
+    benches,
+    bench_minmax_search,
+    bench_evaluated_leaves,
+    bench_board
+);
+criterion_main!(benches);
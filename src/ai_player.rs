@@ -1,17 +1,157 @@
-use crate::{Game, Player};
+use crate::{Game, GameboardError, Player};
 
+/// Returns the central column of a board with the given width: the exact
+/// center for odd widths and the right one of the two central columns for
+/// even widths.
+const fn central_column(width: usize) -> usize {
+    width / 2
+}
+
+/// Optimization: In the first move of each player, take the central column
+/// when it is still empty. Returns `None` when a search is needed.
+fn opening_move<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    allowed: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    let middle = central_column(W);
+    (game.round() < 2 && allowed(middle) && game.board().free_slots_in_column(middle) == H)
+        .then_some(middle)
+}
+
+/// Searches the best move of `player`.
+///
+/// Returns `None` if the game is already over.
 #[must_use]
 pub fn search_best_move<const W: usize, const H: usize>(
     game: &Game<W, H>,
     player: Player,
-) -> usize /* column */ {
-    // Optimization: Take middle when not taken yet
-    if game.round() < 2 {
-        let middle = game.board().width() / 2;
-        if game.board().free_slots_in_column(middle) == game.board().height() {
-            return middle;
-        }
+) -> Option<usize> /* column */ {
+    if let Some(col) = opening_move(game, |_| true) {
+        return Some(col);
     }
 
     super::minmax::minmax_search::<W, H>(game.board().clone(), player)
 }
+
+/// Like [`search_best_move`] but tells why there is no move to search.
+///
+/// # Errors
+/// Fails with [`GameboardError::GameOver`] if a player already won and with
+/// [`GameboardError::NoLegalMoves`] if the board is full. There is no
+/// separate `GameAlreadyWon` variant, as [`GameboardError::GameOver`] already
+/// means exactly that for moves on a won board.
+pub fn try_search_best_move<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    player: Player,
+) -> Result<usize, GameboardError> {
+    if game.board().has_any_winner() {
+        return Err(GameboardError::GameOver);
+    }
+    search_best_move(game, player).ok_or(GameboardError::NoLegalMoves)
+}
+
+/// Like [`search_best_move`] but only considers the `allowed` columns, e.g.,
+/// for puzzle or handicap modes.
+///
+/// Returns `None` if none of the allowed columns is a legal move.
+#[must_use]
+pub fn search_best_move_restricted<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    player: Player,
+    allowed: &[usize],
+) -> Option<usize> {
+    if let Some(col) = opening_move(game, |col| allowed.contains(&col)) {
+        return Some(col);
+    }
+
+    super::minmax::minmax_search_restricted::<W, H>(game.board().clone(), player, allowed)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Game, GameboardError, Player, search_best_move, search_best_move_restricted,
+        try_search_best_move,
+    };
+
+    #[test]
+    fn test_search_best_move_opening() {
+        assert_eq!(
+            search_best_move(&Game::<9, 6>::new(), Player::Player1),
+            Some(4)
+        );
+        assert_eq!(
+            search_best_move(&Game::<8, 6>::new(), Player::Player1),
+            Some(4)
+        );
+        assert_eq!(
+            search_best_move(&Game::<7, 6>::new(), Player::Player1),
+            Some(3)
+        );
+
+        // the second player takes the center as well if it is still empty
+        let mut game = Game::<9, 6>::new();
+        game.insert_player_chip(0, Player::Player1).unwrap();
+        assert_eq!(search_best_move(&game, Player::Player2), Some(4));
+    }
+
+    #[test]
+    fn test_try_search_best_move() {
+        let mut game = Game::<4, 4>::new();
+        let mut player = Player::Player1;
+        for col in [0, 0, 3, 3, 1] {
+            game.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+        assert_eq!(
+            try_search_best_move(&game, player).ok(),
+            search_best_move(&game, player)
+        );
+
+        // player 1 completes the bottom row
+        game.insert_player_chip(0, player).unwrap();
+        game.insert_player_chip(2, player.opponent()).unwrap();
+        assert_eq!(
+            try_search_best_move(&game, player),
+            Err(GameboardError::GameOver)
+        );
+
+        // filled column by column with alternating chips: nobody wins
+        let mut game = Game::<4, 4>::new();
+        for col in [0_usize, 1, 3, 2] {
+            for row in 0..4 {
+                let player = if (row + col / 2).is_multiple_of(2) {
+                    Player::Player1
+                } else {
+                    Player::Player2
+                };
+                game.insert_player_chip(col, player).unwrap();
+            }
+        }
+        assert!(game.board().gameover());
+        assert_eq!(
+            try_search_best_move(&game, Player::Player1),
+            Err(GameboardError::NoLegalMoves)
+        );
+    }
+
+    #[test]
+    fn test_search_best_move_restricted() {
+        let mut game = Game::<4, 4>::new();
+        let mut player = Player::Player1;
+        for col in [0, 0, 3, 3, 1] {
+            game.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+
+        // Blocking column 2 is the only sensible move here
+        let best_move = search_best_move_restricted(&game, player, &[0, 3]).unwrap();
+        assert!([0, 3].contains(&best_move));
+        assert_eq!(search_best_move_restricted(&game, player, &[1, 2]), Some(2));
+        assert_eq!(search_best_move_restricted(&game, player, &[]), None);
+        assert_eq!(search_best_move_restricted(&game, player, &[7]), None);
+
+        let best_move = search_best_move_restricted(&Game::<4, 4>::new(), Player::Player1, &[0, 1]);
+        assert!(best_move.is_some_and(|col| col < 2));
+    }
+}
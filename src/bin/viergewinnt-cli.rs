@@ -16,29 +16,22 @@
 #![deny(missing_debug_implementations)]
 #![deny(rustdoc::all)]
 
-use viergewinnt_rs::{Game, Gameboard, Player, search_best_move};
-
-fn print_board(board: &Gameboard) {
-    // Print rows reverted to that it appears naturally.
-    for row in board.board().iter().rev() {
-        for col in row.iter() {
-            let symbol = match col {
-                None => ' ',
-                Some(Player::Player1) => 'X',
-                Some(Player::Player2) => 'O',
-            };
-            print!("{symbol},");
-        }
-        println!();
-    }
+use viergewinnt_rs::{
+    Game, GameStatus, Gameboard, Player, parse_and_validate_move, search_best_move,
+};
+
+/// Prints the board and highlights the slots of `line`, e.g., the winning
+/// series.
+fn print_board(board: &Gameboard, line: &[(usize, usize)]) {
+    print!("{}", board.to_string_highlighting(line));
 
     for _ in 0..board.width() {
-        print!("--");
+        print!("---");
     }
     println!();
 
     for col_id in (0..board.width()).map(|i| i + 1) {
-        print!("{col_id},");
+        print!(" {col_id} ");
     }
     println!();
 }
@@ -46,20 +39,26 @@ fn print_board(board: &Gameboard) {
 fn main() {
     let mut game = Game::<7, 6>::new();
     let mut current_player = Player::Player1;
+    let mut winning_line = None;
 
     println!("Let's play viergewinnt against the computer.");
     loop {
         println!("----------------");
-        print_board(game.board());
+        print_board(game.board(), &[]);
         println!();
 
-        if game.board().gameover() {
-            println!("Gameover: draft");
+        // wins are reported right after the winning move
+        if game.board().status() == GameStatus::Draw {
+            println!("Gameover: draw");
             break;
         }
 
         // Human player
         if current_player == Player::Player1 {
+            if game.board().in_check(current_player) {
+                println!("Watch out: the computer threatens to win!");
+            }
+
             {
                 print!("Choose your move (column): ");
                 for col in game.board().available_columns_iter().map(|x| x + 1) {
@@ -68,15 +67,20 @@ fn main() {
                 println!();
             }
 
-            let line = {
+            let column = loop {
                 let mut line = String::new();
-                std::io::stdin().read_line(&mut line).unwrap();
-                line.trim().parse::<usize>().unwrap()
+                if std::io::stdin().read_line(&mut line).unwrap() == 0 {
+                    // EOF: nobody is left to play
+                    return;
+                }
+                match parse_and_validate_move(&line, game.board()) {
+                    Ok(column) => break column,
+                    Err(e) => println!("Invalid move ({e}), please try again:"),
+                }
             };
-            // adapt to index
-            let line = line - 1;
 
-            game.insert_player_chip(line, current_player).unwrap();
+            winning_line = game.board().winning_line_after(column, current_player);
+            game.insert_player_chip(column, current_player).unwrap();
 
             {
                 if game.board().check_for_winner(current_player) {
@@ -90,8 +94,12 @@ fn main() {
         // Computer player
         else {
             // let best_move = board.legal_moves_iter().next().unwrap();
-            let best_move = search_best_move::<7, 6>(&game, current_player);
+            let Some(best_move) = search_best_move::<7, 6>(&game, current_player) else {
+                println!("Gameover: no move left for the computer");
+                break;
+            };
             println!("Computer chose column {}", best_move + 1);
+            winning_line = game.board().winning_line_after(best_move, current_player);
             game.insert_player_chip(best_move, current_player).unwrap();
 
             {
@@ -106,5 +114,6 @@ fn main() {
     }
 
     println!("----------------");
-    print_board(game.board());
+    print_board(game.board(), winning_line.as_ref().map_or(&[], |line| line));
+    println!("{}", game.summary());
 }
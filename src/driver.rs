@@ -0,0 +1,63 @@
+//! Drivers that play complete games, e.g., for tournaments between AIs.
+
+use crate::{Game, GameStatus, Player};
+
+/// Plays the game until it is over, starting with `first`.
+///
+/// `choose` is called for every move with the current game and the player
+/// to move and must return a legal column.
+///
+/// # Panics
+/// Panics if `choose` returns an illegal column.
+pub fn play_out<const W: usize, const H: usize>(
+    mut game: Game<W, H>,
+    first: Player,
+    mut choose: impl FnMut(&Game<W, H>, Player) -> usize,
+) -> (Game<W, H>, GameStatus) {
+    let mut player = first;
+    loop {
+        let status = game.board().status();
+        if status != GameStatus::InProgress {
+            return (game, status);
+        }
+
+        let col = choose(&game, player);
+        game.insert_player_chip(col, player)
+            .expect("choose should return a legal column");
+        player = player.opponent();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::driver::play_out;
+    use crate::{Game, GameStatus, Player, search_best_move};
+
+    #[test]
+    fn test_play_out_ai_vs_ai() {
+        let (game, status) = play_out(Game::<4, 4>::new(), Player::Player1, |game, player| {
+            search_best_move(game, player).unwrap()
+        });
+
+        assert_ne!(status, GameStatus::InProgress);
+        assert_eq!(status, game.board().status());
+        match status {
+            GameStatus::Won(player) => assert!(game.board().check_for_winner(player)),
+            GameStatus::Draw => assert!(game.board().gameover()),
+            GameStatus::InProgress => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_play_out_first_column() {
+        let (game, status) = play_out(Game::<4, 4>::new(), Player::Player2, |game, _| {
+            game.board().available_columns_iter().next().unwrap()
+        });
+
+        // Columns are filled one after another with alternating chips, so
+        // player 2 completes the bottom row with the 13th move.
+        assert_eq!(game.history()[0], (0, Player::Player2));
+        assert_eq!(game.round(), 13);
+        assert_eq!(status, GameStatus::Won(Player::Player2));
+    }
+}
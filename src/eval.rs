@@ -0,0 +1,314 @@
+//! Heuristic evaluation of (non-terminal) board positions.
+
+use crate::{Cell, Gameboard, Player};
+
+/// Weights of the individual features of [`evaluate_board`].
+///
+/// All weights except [`Self::parity`] are applied symmetrically: features
+/// of the evaluated player count positive, the same features of the opponent
+/// negative.
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub struct EvalWeights {
+    /// Window of the length of a winning series with two own chips less
+    /// than a series, e.g., two of four, and no opponent chip.
+    pub open_two: i32,
+    /// Window of the length of a winning series with one own chip less than
+    /// a series, e.g., three of four, and no opponent chip.
+    pub open_three: i32,
+    /// Chip in the center column.
+    pub center: i32,
+    /// Open three whose missing slot is in a row that favors the player:
+    /// odd rows (counted from 1) for the evaluated player, even rows for the
+    /// opponent. The rows only depend on the perspective, not on who owns
+    /// the chips, so that the evaluation is symmetric under
+    /// [`Gameboard::swapped`]. In turn, the scores of both players don't
+    /// cancel out for open threes.
+    pub parity: i32,
+    /// Column with two threats on top of each other, see
+    /// [`Gameboard::stacked_threats`].
+    pub stacked_threat: i32,
+    /// Playable slot that creates a fork, see
+    /// [`Gameboard::double_threat_count`]. Only used by [`evaluate_forks`].
+    pub double_threat: i32,
+}
+
+impl Default for EvalWeights {
+    fn default() -> Self {
+        Self {
+            open_two: 2,
+            open_three: 5,
+            center: 3,
+            parity: 2,
+            stacked_threat: 10,
+            double_threat: 20,
+        }
+    }
+}
+
+/// Returns whether `row` (0-based) is a row where threats favor `player`
+/// when the board is evaluated from the perspective of `perspective`.
+fn is_favorable_row(row: usize, player: Player, perspective: Player) -> bool {
+    // rows 1, 3, 5, ... when counted from 1
+    row.is_multiple_of(2) == (player == perspective)
+}
+
+/// Counts the chips of `player`, the chips of the opponent, and the empty
+/// slots in a window, e.g., one emitted by [`Gameboard::for_each_window`].
+#[must_use]
+pub fn window_counts<const K: usize>(
+    cells: &[Cell; K],
+    player: Player,
+) -> (
+    usize, /* own */
+    usize, /* opponent */
+    usize, /* empty */
+) {
+    cells
+        .iter()
+        .fold((0, 0, 0), |(own, opponent, empty), &cell| match cell {
+            Some(p) if p == player => (own + 1, opponent, empty),
+            Some(_) => (own, opponent + 1, empty),
+            None => (own, opponent, empty + 1),
+        })
+}
+
+/// Scores the features of `player` in a single window when the board is
+/// evaluated from the perspective of `perspective`.
+fn evaluate_window<const K: usize>(
+    slots: &[Cell; K],
+    coords: &[(usize, usize); K],
+    player: Player,
+    perspective: Player,
+    weights: &EvalWeights,
+) -> i32 {
+    let (own, other, _) = window_counts(slots, player);
+    if other > 0 {
+        return 0;
+    }
+
+    match own {
+        0 => 0,
+        own if own + 1 == K => {
+            let missing_row = slots
+                .iter()
+                .zip(coords)
+                .find(|(slot, _)| slot.is_none())
+                .map(|(_, &(row, _))| row)
+                .expect("window should have a free slot");
+            if is_favorable_row(missing_row, player, perspective) {
+                weights.open_three + weights.parity
+            } else {
+                weights.open_three
+            }
+        }
+        own if own + 2 == K => weights.open_two,
+        _ => 0,
+    }
+}
+
+/// Heuristically evaluates a board from the perspective of `player`.
+///
+/// Positive scores favor `player`, negative scores the opponent. The score
+/// does not take terminal positions into account, i.e., the caller should
+/// check for a winner first.
+///
+/// Evaluating [`Gameboard::swapped`] for `player` equals evaluating the board
+/// for the opponent.
+#[must_use]
+pub fn evaluate_board<const W: usize, const H: usize, const K: usize>(
+    board: &Gameboard<W, H, K>,
+    player: Player,
+    weights: &EvalWeights,
+) -> i32 {
+    let mut score = 0;
+
+    board.for_each_window(|coords, slots| {
+        score += evaluate_window(&slots, &coords, player, player, weights);
+        score -= evaluate_window(&slots, &coords, player.opponent(), player, weights);
+    });
+
+    score += weights.stacked_threat
+        * (board.stacked_threats(player) as i32 - board.stacked_threats(player.opponent()) as i32);
+
+    let center = W / 2;
+    for row in board.board() {
+        match row[center] {
+            Some(p) if p == player => score += weights.center,
+            Some(_) => score -= weights.center,
+            None => {}
+        }
+    }
+
+    score
+}
+
+/// Scores the forks of `player` minus the forks of the opponent with
+/// [`EvalWeights::double_threat`].
+///
+/// Unlike the features of [`evaluate_board`], finding forks takes a trial
+/// drop per column, which is too expensive for every leaf of a search. It is
+/// meant for single positions instead, e.g., to compare the moves at the
+/// root.
+#[must_use]
+pub fn evaluate_forks<const W: usize, const H: usize, const K: usize>(
+    board: &Gameboard<W, H, K>,
+    player: Player,
+    weights: &EvalWeights,
+) -> i32 {
+    weights.double_threat
+        * (board.double_threat_count(player) as i32
+            - board.double_threat_count(player.opponent()) as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::eval::{EvalWeights, evaluate_board, evaluate_forks, window_counts};
+    use crate::{Gameboard, Player};
+
+    #[test]
+    fn test_evaluate_board_symmetric() {
+        let mut board = Gameboard::<7, 6>::new();
+        let weights = EvalWeights::default();
+        assert_eq!(evaluate_board(&board, Player::Player1, &weights), 0);
+
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(4, Player::Player2).unwrap();
+        let score = evaluate_board(&board, Player::Player1, &weights);
+        assert!(score > 0);
+        assert_eq!(evaluate_board(&board, Player::Player2, &weights), -score);
+    }
+
+    #[test]
+    fn test_evaluate_board_center_weight() {
+        let mut center_board = Gameboard::<7, 6>::new();
+        center_board.insert_player_chip(3, Player::Player1).unwrap();
+        center_board.insert_player_chip(3, Player::Player1).unwrap();
+        center_board.insert_player_chip(0, Player::Player2).unwrap();
+        center_board.insert_player_chip(0, Player::Player2).unwrap();
+
+        let mut side_board = Gameboard::<7, 6>::new();
+        side_board.insert_player_chip(0, Player::Player1).unwrap();
+        side_board.insert_player_chip(0, Player::Player1).unwrap();
+        side_board.insert_player_chip(6, Player::Player2).unwrap();
+        side_board.insert_player_chip(6, Player::Player2).unwrap();
+
+        let default = EvalWeights::default();
+        let boosted = EvalWeights {
+            center: default.center * 10,
+            ..default
+        };
+
+        let diff = |weights: &EvalWeights| {
+            evaluate_board(&center_board, Player::Player1, weights)
+                - evaluate_board(&side_board, Player::Player1, weights)
+        };
+        assert!(diff(&boosted) > diff(&default));
+    }
+
+    #[test]
+    fn test_window_counts() {
+        let p1 = Some(Player::Player1);
+        let p2 = Some(Player::Player2);
+
+        assert_eq!(window_counts(&[None; 4], Player::Player1), (0, 0, 4));
+        assert_eq!(window_counts(&[p1; 4], Player::Player1), (4, 0, 0));
+        assert_eq!(window_counts(&[p1; 4], Player::Player2), (0, 4, 0));
+        assert_eq!(
+            window_counts(&[p1, None, p2, p1], Player::Player1),
+            (2, 1, 1)
+        );
+        assert_eq!(
+            window_counts(&[p1, None, p2, p1], Player::Player2),
+            (1, 2, 1)
+        );
+        assert_eq!(
+            window_counts(&[None, p2, p2, p2], Player::Player2),
+            (3, 0, 1)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_board_stacked_threat() {
+        let mut board = Gameboard::<7, 6>::new();
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+            board.insert_player_chip(col, Player::Player2).unwrap();
+        }
+        // stack a second row of player 1 chips instead
+        let mut stacked = Gameboard::<7, 6>::new();
+        for col in 0..3 {
+            stacked.insert_player_chip(col, Player::Player1).unwrap();
+            stacked.insert_player_chip(col, Player::Player1).unwrap();
+        }
+
+        let default = EvalWeights::default();
+        let without = EvalWeights {
+            stacked_threat: 0,
+            ..default
+        };
+        assert_eq!(
+            evaluate_board(&stacked, Player::Player1, &default)
+                - evaluate_board(&stacked, Player::Player1, &without),
+            default.stacked_threat
+        );
+        assert_eq!(
+            evaluate_board(&board, Player::Player1, &default),
+            evaluate_board(&board, Player::Player1, &without)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_forks() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+
+        let default = EvalWeights::default();
+        let without = EvalWeights {
+            double_threat: 0,
+            ..default
+        };
+        assert_eq!(
+            evaluate_forks(&board, Player::Player1, &default),
+            default.double_threat
+        );
+        assert_eq!(
+            evaluate_forks(&board, Player::Player2, &default),
+            -default.double_threat
+        );
+        // forks aren't part of the per-leaf evaluation
+        assert_eq!(
+            evaluate_board(&board, Player::Player1, &default),
+            evaluate_board(&board, Player::Player1, &without)
+        );
+    }
+
+    #[test]
+    fn test_evaluate_board_swapped() {
+        let mut board = Gameboard::<7, 6>::new();
+        for (col, player) in [
+            (3, Player::Player1),
+            (3, Player::Player2),
+            (2, Player::Player1),
+            (4, Player::Player2),
+            (2, Player::Player1),
+        ] {
+            board.insert_player_chip(col, player).unwrap();
+        }
+
+        let weights = EvalWeights::default();
+        // the open three misses a slot in an odd row
+        let open_three = Gameboard::<7, 6>::from_ascii_lenient("XXX..OO").unwrap();
+        for board in [board, open_three] {
+            for player in [Player::Player1, Player::Player2] {
+                assert_eq!(
+                    evaluate_board(&board.swapped(), player, &weights),
+                    evaluate_board(&board, player.opponent(), &weights)
+                );
+            }
+        }
+    }
+}
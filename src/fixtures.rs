@@ -0,0 +1,54 @@
+//! Fixed positions, e.g., for benchmarks and tests.
+
+use crate::Game;
+use alloc::vec::Vec;
+
+/// Move sequences on a 7x6 board that lead to mid-game positions. The
+/// players alternate, starting with [`crate::Player::Player1`].
+pub const MIDGAME_MOVES: [&[usize]; 3] = [
+    &[3, 3, 2, 4, 4, 2, 5, 1, 3, 3, 1, 0],
+    &[3, 2, 3, 3, 4, 5, 2, 4, 6, 5, 0, 1, 1, 5],
+    &[0, 6, 1, 5, 3, 3, 4, 2, 2, 4, 6, 0],
+];
+
+/// Returns the games after replaying each of [`MIDGAME_MOVES`].
+///
+/// # Panics
+/// Panics if a fixture contains an illegal move.
+#[must_use]
+pub fn midgame_positions() -> Vec<Game<7, 6>> {
+    MIDGAME_MOVES
+        .iter()
+        .map(|moves| {
+            let mut game = Game::new();
+            for &col in *moves {
+                let player = game.turn();
+                game.insert_player_chip(col, player)
+                    .expect("fixture should be legal");
+            }
+            game
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fixtures::{MIDGAME_MOVES, midgame_positions};
+    use crate::{GameStatus, Gameboard, validate_moves};
+
+    #[test]
+    fn test_midgame_positions() {
+        let games = midgame_positions();
+        assert_eq!(games.len(), MIDGAME_MOVES.len());
+        for (game, moves) in games.iter().zip(MIDGAME_MOVES) {
+            assert_eq!(validate_moves::<7, 6>(moves), Ok(()));
+            assert_eq!(game.round(), moves.len());
+            assert_eq!(game.board().status(), GameStatus::InProgress);
+            // gravity-valid
+            assert_eq!(
+                Gameboard::from_cells(*game.board().board()).as_ref(),
+                Ok(game.board())
+            );
+        }
+    }
+}
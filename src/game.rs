@@ -1,17 +1,44 @@
 //! Game logic and game board.
 
+use crate::{EvalWeights, Rng, evaluate_board};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
 use core::error::Error;
 use core::fmt::{Debug, Formatter};
 use core::{cmp, fmt};
 
-/// Number of coins in a row to win the game.
-const SERIES_LEN: usize = 4;
+/// Number of coins in a row to win the game in the standard rules, the
+/// default series length of [`Gameboard`].
+pub const SERIES_LEN: usize = 4;
 
 #[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Eq)]
 pub enum GameboardError {
     /// Column is full.
     ColumnFull,
     InvalidColumn,
+    /// Row doesn't exist.
+    InvalidRow,
+    /// Input couldn't be parsed, e.g., as a column or as a board.
+    InvalidInput,
+    /// A chip is placed above an empty slot.
+    FloatingChip,
+    /// Both players have a winning series.
+    MultipleWinners,
+    /// A player already won the game.
+    GameOver,
+    /// There is no move that could be undone, e.g., no move was made yet or
+    /// the column has no chips.
+    NothingToUndo,
+    /// The board is full.
+    NoLegalMoves,
+    /// The board is too small to fit a winning series.
+    BoardTooSmall {
+        width: usize,
+        height: usize,
+        /// Minimum width and height.
+        required: usize,
+    },
 }
 
 impl fmt::Display for GameboardError {
@@ -22,11 +49,258 @@ impl fmt::Display for GameboardError {
 
 impl Error for GameboardError {}
 
+/// Error when replaying a sequence of moves.
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Eq)]
+pub struct ReplayError {
+    /// Index of the first illegal move.
+    pub index: usize,
+    /// Why the move is illegal.
+    pub error: GameboardError,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        write!(f, "illegal move at index {}", self.index)
+    }
+}
+
+impl Error for ReplayError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Error when appending a move line, see [`Game::append_move_line`].
+#[derive(Debug, PartialOrd, PartialEq, Clone, Copy, Eq)]
+pub enum MoveLineError {
+    /// The move is illegal.
+    IllegalMove(GameboardError),
+    /// Writing the line failed.
+    Write(fmt::Error),
+}
+
+impl fmt::Display for MoveLineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), fmt::Error> {
+        match self {
+            Self::IllegalMove(_) => write!(f, "illegal move"),
+            Self::Write(_) => write!(f, "writing the move line failed"),
+        }
+    }
+}
+
+impl Error for MoveLineError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::IllegalMove(error) => Some(error),
+            Self::Write(error) => Some(error),
+        }
+    }
+}
+
+/// Parses a 1-indexed column from user input and validates that inserting a
+/// chip there is legal.
+///
+/// Returns the 0-indexed column on success.
+pub fn parse_and_validate_move<const W: usize, const H: usize>(
+    input: &str,
+    board: &Gameboard<W, H>,
+) -> Result<usize, GameboardError> {
+    let column = input
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| GameboardError::InvalidInput)?;
+    let column_index = column
+        .checked_sub(1)
+        .filter(|&col| col < W)
+        .ok_or(GameboardError::InvalidColumn)?;
+
+    if board.free_slots_in_column(column_index) == 0 {
+        return Err(GameboardError::ColumnFull);
+    }
+    Ok(column_index)
+}
+
+/// Replays the moves, alternating between the players starting with
+/// [`Player::Player1`], and checks that each of them is legal.
+///
+/// Moves after a player has won are illegal as well.
+///
+/// # Errors
+/// Returns the index of the first illegal move and the reason.
+pub fn validate_moves<const W: usize, const H: usize>(moves: &[usize]) -> Result<(), ReplayError> {
+    let mut board = Gameboard::<W, H>::new();
+    let mut player = Player::Player1;
+    for (index, &col) in moves.iter().enumerate() {
+        if board.check_for_winner(player.opponent()) {
+            return Err(ReplayError {
+                index,
+                error: GameboardError::GameOver,
+            });
+        }
+        board
+            .insert_player_chip(col, player)
+            .map_err(|error| ReplayError { index, error })?;
+        player = player.opponent();
+    }
+    Ok(())
+}
+
+/// Replays the first `ply` moves, e.g., to step through a recorded game in a
+/// viewer.
+///
+/// The players alternate, starting with [`Player::Player1`]. All moves are
+/// replayed if `ply` exceeds their number.
+///
+/// # Errors
+/// Fails if one of the replayed moves is illegal, see [`validate_moves`].
+pub fn replay_up_to<const W: usize, const H: usize>(
+    moves: &[usize],
+    ply: usize,
+) -> Result<Game<W, H>, GameboardError> {
+    let mut game = Game::new();
+    for &col in moves.iter().take(ply) {
+        if game.board().has_any_winner() {
+            return Err(GameboardError::GameOver);
+        }
+        game.insert_player_chip(col, game.turn())?;
+    }
+    Ok(game)
+}
+
+/// Statistics of one player over a game, see [`collect_stats`].
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct PlayerStats {
+    /// Number of chips the player inserted.
+    pub moves: usize,
+    /// Number of chips the player inserted into the central column.
+    pub center_moves: usize,
+    /// Number of empty slots that complete a series of the player and that
+    /// were created by the player's moves.
+    pub threats_created: usize,
+    /// Number of moves that took away a slot completing a series of the
+    /// opponent.
+    pub blocks_made: usize,
+}
+
+/// Replays the moves, alternating between the players starting with `first`,
+/// and tallies the statistics of each player. The stats of
+/// [`Player::Player1`] come first.
+///
+/// The replay stops at the first illegal move.
+#[must_use]
+pub fn collect_stats<const W: usize, const H: usize>(
+    moves: &[usize],
+    first: Player,
+) -> [PlayerStats; 2] {
+    let count_threats = |board: &Gameboard<W, H>, player| {
+        board
+            .threat_slots(player)
+            .iter()
+            .flatten()
+            .filter(|&&threat| threat)
+            .count()
+    };
+
+    let mut stats = [PlayerStats::default(); 2];
+    let mut board = Gameboard::<W, H>::new();
+    let mut player = first;
+    for &col in moves {
+        let threats_before = count_threats(&board, player);
+        let blocks = !board.threats_blocked_by(col, player).is_empty();
+        if board.insert_player_chip(col, player).is_err() {
+            break;
+        }
+
+        let player_stats = &mut stats[usize::from(player.as_u8() - 1)];
+        player_stats.moves += 1;
+        // the exact center for odd widths, the right one of the two central
+        // columns for even widths
+        if col == W / 2 {
+            player_stats.center_moves += 1;
+        }
+        player_stats.threats_created +=
+            count_threats(&board, player).saturating_sub(threats_before);
+        if blocks {
+            player_stats.blocks_made += 1;
+        }
+        player = player.opponent();
+    }
+    stats
+}
+
 /// Game with all its state.
-#[derive(Debug, PartialOrd, PartialEq, Clone, Eq)]
+#[derive(Debug, PartialEq, Clone, Eq)]
 pub struct Game<const W: usize = 7, const H: usize = 6> {
     board: Gameboard<W, H>,
     round: usize,
+    /// All moves so far: (column, player).
+    history: Vec<(usize, Player)>,
+    /// Column of each move in `history`, see [`Self::recent_moves`].
+    columns: Vec<usize>,
+    /// Timestamp of each move in `history`, see
+    /// [`Self::insert_with_timestamp`].
+    timestamps: Vec<u64>,
+}
+
+/// Saved state of a [`Game`], see [`Game::snapshot`].
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct GameSnapshot<const W: usize = 7, const H: usize = 6> {
+    board: Gameboard<W, H>,
+    round: usize,
+    history: Vec<(usize, Player)>,
+    columns: Vec<usize>,
+    timestamps: Vec<u64>,
+}
+
+impl<const W: usize, const H: usize> GameSnapshot<W, H> {
+    #[must_use]
+    pub const fn board(&self) -> &Gameboard<W, H> {
+        &self.board
+    }
+
+    #[must_use]
+    pub const fn round(&self) -> usize {
+        self.round
+    }
+
+    #[must_use]
+    pub fn history(&self) -> &[(usize, Player)] {
+        &self.history
+    }
+
+    /// Returns the player whose turn it is.
+    #[must_use]
+    pub fn turn(&self) -> Player {
+        next_turn(&self.history, &self.board)
+    }
+}
+
+/// Returns the player whose turn it is after the given moves.
+///
+/// If the moves are unknown, e.g., after [`Game::edit`], the player with
+/// fewer chips on `board` is to move, and [`Player::Player1`] on a tie.
+fn next_turn<const W: usize, const H: usize>(
+    history: &[(usize, Player)],
+    board: &Gameboard<W, H>,
+) -> Player {
+    history.last().map_or_else(
+        || {
+            let chips = |player| {
+                board
+                    .board()
+                    .iter()
+                    .flatten()
+                    .filter(|&&slot| slot == Some(player))
+                    .count()
+            };
+            if chips(Player::Player2) < chips(Player::Player1) {
+                Player::Player2
+            } else {
+                Player::Player1
+            }
+        },
+        |&(_, player)| player.opponent(),
+    )
 }
 
 impl<const W: usize, const H: usize> Game<W, H> {
@@ -38,16 +312,53 @@ impl<const W: usize, const H: usize> Game<W, H> {
         Self {
             board: Gameboard::new(),
             round: 0,
+            history: Vec::new(),
+            columns: Vec::new(),
+            timestamps: Vec::new(),
         }
     }
 
+    /// Like [`Self::new`] but fails instead of panicking if the board is too
+    /// small.
+    ///
+    /// # Errors
+    /// See [`Gameboard::try_new`].
+    pub fn try_new() -> Result<Self, GameboardError> {
+        Ok(Self {
+            board: Gameboard::try_new()?,
+            round: 0,
+            history: Vec::new(),
+            columns: Vec::new(),
+            timestamps: Vec::new(),
+        })
+    }
+
     pub fn insert_player_chip(
         &mut self,
         column_index: usize,
         player: Player,
     ) -> Result<(), GameboardError> {
-        self.board.insert_player_chip(column_index, player)?;
+        let timestamp = self.timestamps.last().copied().unwrap_or(0);
+        self.insert_with_timestamp(column_index, player, timestamp)
+    }
+
+    /// Like [`Self::insert_player_chip`] but records the caller-supplied
+    /// timestamp `ts` of the move, e.g., for timed matches. The unit is up
+    /// to the caller.
+    ///
+    /// # Errors
+    /// Fails if the move is illegal.
+    pub fn insert_with_timestamp(
+        &mut self,
+        column: usize,
+        player: Player,
+        ts: u64,
+    ) -> Result<(), GameboardError> {
+        self.board.insert_player_chip(column, player)?;
         self.round += 1;
+        self.history.push((column, player));
+        self.columns.push(column);
+        self.timestamps.push(ts);
         Ok(())
     }
 
@@ -56,10 +367,181 @@ impl<const W: usize, const H: usize> Game<W, H> {
         &self.board
     }
 
+    /// Consumes the game and returns its board, without cloning it.
+    #[must_use]
+    pub fn into_board(self) -> Gameboard<W, H> {
+        self.board
+    }
+
     #[must_use]
     pub const fn round(&self) -> usize {
         self.round
     }
+
+    /// Takes back the last move and returns it as `(column, player)`.
+    ///
+    /// # Errors
+    /// Fails with [`GameboardError::NothingToUndo`] if no move was made yet.
+    pub fn undo(&mut self) -> Result<(usize, Player), GameboardError> {
+        let (column, player) = self.history.pop().ok_or(GameboardError::NothingToUndo)?;
+        let removed = self.board.remove_top_chip(column);
+        debug_assert_eq!(removed, Some(player));
+        self.columns.pop();
+        self.timestamps.pop();
+        self.round -= 1;
+        Ok((column, player))
+    }
+
+    /// Returns all moves so far as `(column, player)`, oldest first.
+    #[must_use]
+    pub fn history(&self) -> &[(usize, Player)] {
+        &self.history
+    }
+
+    /// Returns the columns of up to the last `n` moves, oldest first, e.g.,
+    /// to fade older moves in a UI.
+    #[must_use]
+    pub fn recent_moves(&self, n: usize) -> &[usize] {
+        &self.columns[self.columns.len().saturating_sub(n)..]
+    }
+
+    /// Returns the timestamp of each move, aligned with [`Self::history`].
+    ///
+    /// Moves without a timestamp, i.e., made with
+    /// [`Self::insert_player_chip`], inherit the timestamp of the previous
+    /// move (`0` for the first move), so that they take no time.
+    #[must_use]
+    pub fn move_timestamps(&self) -> &[u64] {
+        &self.timestamps
+    }
+
+    /// Returns the player who made the `index`-th move (0-indexed), e.g., to
+    /// annotate a replay. Returns `None` if the move wasn't made yet.
+    #[must_use]
+    pub fn player_at_move(&self, index: usize) -> Option<Player> {
+        self.history.get(index).map(|&(_, player)| player)
+    }
+
+    /// Returns the player whose turn it is, i.e., the opponent of the player
+    /// who made the last move. [`Player::Player1`] begins.
+    #[must_use]
+    pub fn turn(&self) -> Player {
+        next_turn(&self.history, &self.board)
+    }
+
+    /// Returns a one-line summary of the game, e.g., `Player 1 won in 7
+    /// moves` or `Draw after 42 moves`.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        match self.board.status() {
+            GameStatus::Won(player) => {
+                format!("Player {} won in {} moves", player.as_u8(), self.round)
+            }
+            GameStatus::Draw => format!("Draw after {} moves", self.round),
+            GameStatus::InProgress => format!("In progress after {} moves", self.round),
+        }
+    }
+
+    /// Edits the board with `f`, e.g., in a board editor, and updates the
+    /// state of the game to match the resulting board.
+    ///
+    /// The order of the chips is unknown afterwards, so the history is
+    /// cleared and the round is set to the number of chips. The player with
+    /// fewer chips is to move next.
+    pub fn edit(&mut self, f: impl FnOnce(&mut Gameboard<W, H>)) {
+        f(&mut self.board);
+        self.round = W * H - self.board.remaining_moves();
+        self.history.clear();
+        self.columns.clear();
+        self.timestamps.clear();
+    }
+
+    /// Saves the current state of the game.
+    #[must_use]
+    pub fn snapshot(&self) -> GameSnapshot<W, H> {
+        GameSnapshot {
+            board: self.board.clone(),
+            round: self.round,
+            history: self.history.clone(),
+            columns: self.columns.clone(),
+            timestamps: self.timestamps.clone(),
+        }
+    }
+
+    /// Replaces the state of the game with a previously saved state.
+    pub fn restore(&mut self, snapshot: GameSnapshot<W, H>) {
+        let GameSnapshot {
+            board,
+            round,
+            history,
+            columns,
+            timestamps,
+        } = snapshot;
+        *self = Self {
+            board,
+            round,
+            history,
+            columns,
+            timestamps,
+        };
+    }
+
+    /// Writes one line for the move of the player whose turn it is into
+    /// `column`: the 1-indexed column and the [`GameStatus::code`] of the
+    /// game after the move, e.g., `4 -`.
+    ///
+    /// The move is only simulated, i.e., the caller still has to make it.
+    /// Appending a line per move yields a log that can be tailed.
+    ///
+    /// # Errors
+    /// Fails with [`MoveLineError::IllegalMove`] if the move is illegal,
+    /// without writing anything, and with [`MoveLineError::Write`] if
+    /// writing fails.
+    pub fn append_move_line(
+        &self,
+        out: &mut impl fmt::Write,
+        column: usize,
+    ) -> Result<(), MoveLineError> {
+        let mut board = self.board.clone();
+        board
+            .insert_player_chip(column, self.turn())
+            .map_err(MoveLineError::IllegalMove)?;
+        writeln!(out, "{} {}", column + 1, board.status().code()).map_err(MoveLineError::Write)
+    }
+
+    /// Returns the number of moves until the board is full.
+    #[must_use]
+    pub fn moves_until_full(&self) -> usize {
+        self.board.remaining_moves()
+    }
+
+    /// Returns whether exactly one legal move is left.
+    #[must_use]
+    pub fn is_last_move(&self) -> bool {
+        self.moves_until_full() == 1
+    }
+
+    /// Returns whether both games are in the same position, i.e., have the
+    /// same board and the same player to move, regardless of the order of
+    /// the moves that led there.
+    #[must_use]
+    pub fn same_position(&self, other: &Self) -> bool {
+        self.board == other.board && self.turn() == other.turn()
+    }
+
+    /// Returns a deterministic key of the move history, e.g., to deduplicate
+    /// replays. Unlike [`Self::same_position`], the order of the moves
+    /// matters.
+    ///
+    /// Games that only differ in moves made before [`Self::edit`] have the
+    /// same key, as editing resets the history.
+    #[must_use]
+    pub fn game_key(&self) -> u64 {
+        self.history.iter().fold(0, |key, &(column, player)| {
+            let chip = ((column as u64) << 8) | u64::from(player.as_u8());
+            Rng::new(key ^ chip).next_u64()
+        })
+    }
 }
 
 impl<const W: usize, const H: usize> Default for Game<W, H> {
@@ -68,38 +550,298 @@ impl<const W: usize, const H: usize> Default for Game<W, H> {
     }
 }
 
+impl<const W: usize, const H: usize> From<Game<W, H>> for Gameboard<W, H> {
+    fn from(game: Game<W, H>) -> Self {
+        game.into_board()
+    }
+}
+
 /// Gameboard.
-#[derive(Debug, PartialOrd, PartialEq, Clone, Eq)]
-pub struct Gameboard<const W: usize = 7, const H: usize = 6>(
+///
+/// A player wins by connecting `K` chips in a row, [`SERIES_LEN`] in the
+/// standard rules.
+///
+/// Boards intentionally can't be ordered, as there is no meaningful order of
+/// positions:
+///
+/// ```compile_fail
+/// use viergewinnt_rs::Gameboard;
+/// let _ = Gameboard::<7, 6>::new() < Gameboard::<7, 6>::new();
+/// ```
+#[derive(PartialEq, Clone, Eq)]
+pub struct Gameboard<const W: usize = 7, const H: usize = 6, const K: usize = SERIES_LEN>(
     /*
      * Board: rows --> col --> field
      * Technical indices correspond to the logical indices:
      * (row=0,col=0) <==> bottom left of game board
      */
-    [[Option<Player>; W]; H],
+    [[Cell; W]; H],
+    /*
+     * Legal moves: entry `col` is set if the top slot of the column is free.
+     * Must be updated whenever the top row changes.
+     */
+    [bool; W],
+    /*
+     * Scoring height: rows from this index upwards are a buffer that
+     * doesn't count for wins. Equals `H` for the standard rules.
+     */
+    usize,
 );
 
-impl<const W: usize, const H: usize> Default for Gameboard<W, H> {
+impl<const W: usize, const H: usize, const K: usize> Default for Gameboard<W, H, K> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<const W: usize, const H: usize> Gameboard<W, H> {
+/// Prints the rows top row first, one string per row in the notation of
+/// [`Gameboard::column_string`], instead of nested arrays of slots. This
+/// keeps test failures readable. The legal moves are derived from the slots
+/// and therefore left out.
+impl<const W: usize, const H: usize, const K: usize> Debug for Gameboard<W, H, K> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .0
+            .iter()
+            .rev()
+            .map(|row| {
+                row.iter()
+                    .map(|&cell| debug_symbol(cell))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        f.debug_struct("Gameboard")
+            .field("rows", &rows)
+            .field("scoring_height", &self.2)
+            .finish()
+    }
+}
+
+/// Returns the symbol of a slot in [`Gameboard::column_string`] and in the
+/// [`Debug`] output of boards.
+const fn debug_symbol(cell: Cell) -> char {
+    match cell {
+        None => '.',
+        Some(Player::Player1) => 'X',
+        Some(Player::Player2) => 'O',
+    }
+}
+
+impl<const W: usize, const H: usize, const K: usize> Gameboard<W, H, K> {
+    /// Creates an empty board.
+    ///
+    /// # Panics
+    /// Panics if the board can't fit a winning series, see [`Self::try_new`].
     #[must_use]
     pub const fn new() -> Self {
-        assert!(W >= SERIES_LEN);
-        assert!(H >= SERIES_LEN);
+        assert!(K > 0);
+        assert!(W >= K);
+        assert!(H >= K);
 
         let board = [[None; W]; H];
-        Self(board)
+        Self(board, Self::legal_mask(&board), H)
+    }
+
+    /// Creates a board for a variant where only the lowest `scoring_height`
+    /// rows count for wins; the rows above are a buffer.
+    ///
+    /// # Errors
+    /// Fails with [`GameboardError::InvalidRow`] if `scoring_height` exceeds
+    /// the height of the board.
+    pub const fn with_scoring_height(scoring_height: usize) -> Result<Self, GameboardError> {
+        if scoring_height > H {
+            return Err(GameboardError::InvalidRow);
+        }
+        let mut board = Self::new();
+        board.2 = scoring_height;
+        Ok(board)
+    }
+
+    /// Like [`Self::new`] but fails instead of panicking if the board is too
+    /// small, e.g., for sizes chosen by users.
+    ///
+    /// # Errors
+    /// Fails with [`GameboardError::BoardTooSmall`] if the board can't fit a
+    /// winning series.
+    pub const fn try_new() -> Result<Self, GameboardError> {
+        if let Err(e) = Self::check_size() {
+            return Err(e);
+        }
+        Ok(Self::new())
+    }
+
+    /// Checks that the board can fit a winning series in each direction.
+    const fn check_size() -> Result<(), GameboardError> {
+        if W < K || H < K {
+            return Err(GameboardError::BoardTooSmall {
+                width: W,
+                height: H,
+                required: K,
+            });
+        }
+        Ok(())
+    }
+
+    /// Creates a board from raw slots, e.g., when loading a position.
+    ///
+    /// The slots use the same layout as [`Self::board`]. Fails if the board
+    /// is too small, if a chip is placed above an empty slot, or if both
+    /// players have a winning series.
+    pub fn from_cells(cells: [[Cell; W]; H]) -> Result<Self, GameboardError> {
+        Self::check_size()?;
+        let board = Self(cells, Self::legal_mask(&cells), H);
+        board.validate()?;
+        Ok(board)
+    }
+
+    /// Parses a board drawn with one line per row, top row first, and one
+    /// character per slot: `X` for [`Player::Player1`], `O` for
+    /// [`Player::Player2`], and `.` or a space for empty slots (all
+    /// case-insensitive).
+    ///
+    /// The input may be ragged: short lines are padded with empty slots on
+    /// the right, missing lines are empty rows at the top, and trailing
+    /// whitespace is ignored. This is handy for hand-written positions in
+    /// tools and tests.
+    ///
+    /// # Errors
+    /// Fails with [`GameboardError::InvalidInput`] if a line is wider than
+    /// the board, if there are more lines than rows, or on an unknown
+    /// symbol. Otherwise, the same checks as in [`Self::from_cells`] apply.
+    pub fn from_ascii_lenient(s: &str) -> Result<Self, GameboardError> {
+        let lines = s.lines().map(str::trim_end).collect::<Vec<_>>();
+        if lines.len() > H {
+            return Err(GameboardError::InvalidInput);
+        }
+
+        let mut cells = [[None; W]; H];
+        // the last line is the bottom row
+        for (row, line) in lines.iter().rev().enumerate() {
+            if line.chars().count() > W {
+                return Err(GameboardError::InvalidInput);
+            }
+            for (col, symbol) in line.chars().enumerate() {
+                cells[row][col] = match symbol.to_ascii_uppercase() {
+                    'X' => Some(Player::Player1),
+                    'O' => Some(Player::Player2),
+                    '.' | ' ' => None,
+                    _ => return Err(GameboardError::InvalidInput),
+                };
+            }
+        }
+        Self::from_cells(cells)
+    }
+
+    /// Checks that no chip is placed above an empty slot and that not both
+    /// players have a winning series.
+    ///
+    /// # Errors
+    /// Returns the first violation that was found.
+    pub fn validate(&self) -> Result<(), GameboardError> {
+        for col in 0..W {
+            let height = H - self.free_slots_in_column(col);
+            if self.chips_above(height, col) > 0 {
+                return Err(GameboardError::FloatingChip);
+            }
+        }
+
+        if self.check_for_winner(Player::Player1) && self.check_for_winner(Player::Player2) {
+            return Err(GameboardError::MultipleWinners);
+        }
+
+        Ok(())
+    }
+
+    /// Returns whether the position can arise in a game where the players
+    /// alternate, i.e., whether the board is valid according to
+    /// [`Self::validate`] and neither player has more than one chip more
+    /// than the other.
+    ///
+    /// This catches impossible positions, e.g., from external sources.
+    #[must_use]
+    pub fn is_reachable(&self) -> bool {
+        let chips = |player| {
+            self.0
+                .iter()
+                .flatten()
+                .filter(|&&slot| slot == Some(player))
+                .count()
+        };
+        self.validate().is_ok() && chips(Player::Player1).abs_diff(chips(Player::Player2)) <= 1
+    }
+
+    /// Sets a single slot, e.g., in a board editor.
+    ///
+    /// The board isn't validated, as intermediate states of an edit may
+    /// violate the rules. Call [`Self::validate`] when the edit is done.
+    ///
+    /// # Errors
+    /// Fails if the slot doesn't exist.
+    pub const fn set_cell(
+        &mut self,
+        row: usize,
+        col: usize,
+        value: Cell,
+    ) -> Result<(), GameboardError> {
+        if row >= H {
+            return Err(GameboardError::InvalidRow);
+        }
+        if col >= W {
+            return Err(GameboardError::InvalidColumn);
+        }
+        self.0[row][col] = value;
+        if row == H - 1 {
+            self.1 = Self::legal_mask(&self.0);
+        }
+        Ok(())
     }
 
     #[must_use]
-    pub const fn board(&self) -> &[[Option<Player>; W]; H] {
+    pub const fn board(&self) -> &[[Cell; W]; H] {
         &self.0
     }
 
+    /// Returns an owned copy of all rows, starting with the bottom row.
+    #[must_use]
+    pub fn rows_bottom_up(&self) -> Vec<[Cell; W]> {
+        self.0.to_vec()
+    }
+
+    /// Returns an owned copy of all rows, starting with the top row.
+    ///
+    /// This is the order in which the board is usually printed.
+    #[must_use]
+    pub fn rows_top_down(&self) -> Vec<[Cell; W]> {
+        self.0.iter().rev().copied().collect()
+    }
+
+    /// Returns the owner of the slot in column `col` and in row `display_row`
+    /// counted from the top, i.e., in the order the board is printed. This
+    /// avoids mixing up the printed order with the internal one, where row
+    /// `0` is the bottom row.
+    ///
+    /// Returns `None` if the slot is empty or doesn't exist.
+    #[must_use]
+    pub fn logical_cell(&self, display_row: usize, col: usize) -> Option<Player> {
+        let row = H.checked_sub(display_row)?.checked_sub(1)?;
+        *self.0.get(row)?.get(col)?
+    }
+
+    /// Returns a heap-allocated copy of the board, rows bottom-up, for
+    /// consumers that can't handle const generics.
+    #[must_use]
+    pub fn to_nested_vec(&self) -> Vec<Vec<Cell>> {
+        self.0.iter().map(|row| row.to_vec()).collect()
+    }
+
+    /// Renders a single column bottom-to-top, with `X` for
+    /// [`Player::Player1`], `O` for [`Player::Player2`], and `.` for empty
+    /// slots.
+    #[must_use]
+    pub fn column_string(&self, column: usize) -> String {
+        self.0.iter().map(|row| debug_symbol(row[column])).collect()
+    }
+
     /// Returns the index to the next free slot in the selected column.
     ///
     /// Returns `None` if there are no more free slots.
@@ -107,9 +849,25 @@ impl<const W: usize, const H: usize> Gameboard<W, H> {
         (0..H).find(|&row_index| self.0[row_index][column_index].is_none())
     }
 
+    /// Returns the mask of legal moves, see the second field of
+    /// [`Gameboard`].
+    const fn legal_mask(cells: &[[Cell; W]; H]) -> [bool; W] {
+        let mut mask = [false; W];
+        let mut col = 0;
+        while col < W {
+            mask[col] = cells[H - 1][col].is_none();
+            col += 1;
+        }
+        mask
+    }
+
     /// Emits the column indices where moves are legal.
-    pub fn available_columns_iter(&self) -> impl Iterator<Item = usize> {
-        (0..W).filter(|&col| self.next_slot_in_column(col).is_some())
+    ///
+    /// The iterator doesn't borrow the board, so the board can be modified
+    /// while iterating.
+    pub fn available_columns_iter(&self) -> impl Iterator<Item = usize> + use<W, H, K> {
+        let mask = self.1;
+        (0..W).filter(move |&col| mask[col])
     }
 
     /// Returns the number of free slots in the given column.
@@ -123,72 +881,409 @@ impl<const W: usize, const H: usize> Gameboard<W, H> {
         0
     }
 
-    /// Returns whether the game is over, i.e., there are no legal moves.
+    /// Returns the number of chips in each column.
     #[must_use]
-    pub fn gameover(&self) -> bool {
-        self.available_columns_iter().count() == 0
+    pub fn column_heights(&self) -> [usize; W] {
+        core::array::from_fn(|col| H - self.free_slots_in_column(col))
     }
 
-    pub(crate) fn insert_player_chip(
-        &mut self,
-        column_index: usize,
-        player: Player,
-    ) -> Result<(), GameboardError> {
-        if column_index >= W {
-            return Err(GameboardError::InvalidColumn);
-        }
+    /// Returns the number of chips in the lowest column, e.g., to tell flat
+    /// from uneven boards together with [`Self::max_height`].
+    #[must_use]
+    pub fn min_height(&self) -> usize {
+        self.column_heights().into_iter().min().unwrap_or(0)
+    }
 
-        let row_index = self
-            .next_slot_in_column(column_index)
-            .ok_or(GameboardError::ColumnFull)?;
-        self.0[row_index][column_index] = Some(player);
-        Ok(())
+    /// Returns the number of chips in the highest column.
+    #[must_use]
+    pub fn max_height(&self) -> usize {
+        self.column_heights().into_iter().max().unwrap_or(0)
     }
 
-    fn check_for_winner_vertically(&self, player: Player) -> bool {
-        // check vertically
-        for col in 0..W {
-            let rows_to_check = H - SERIES_LEN + 1;
-            for row in 0..rows_to_check {
-                if self.0[row][col] == Some(player)
-                    && self.0[row + 1][col] == Some(player)
-                    && self.0[row + 2][col] == Some(player)
-                    && self.0[row + 3][col] == Some(player)
-                {
-                    return true;
-                }
-            }
+    /// Returns the number of chips that have to be dropped into `column`
+    /// until one lands in `row`, including that chip, e.g., to plan when a
+    /// threat becomes playable.
+    ///
+    /// Returns `None` if the slot is already taken or doesn't exist.
+    #[must_use]
+    pub fn drops_until_row(&self, column: usize, row: usize) -> Option<usize> {
+        if column >= W || row >= H {
+            return None;
         }
-
-        false
+        let next_row = self.next_slot_in_column(column)?;
+        (next_row <= row).then(|| row - next_row + 1)
     }
 
-    fn check_for_winner_horizontally(&self, player: Player) -> bool {
-        // check vertically
-        for row in 0..H {
-            let cols_to_check = W - SERIES_LEN + 1;
-            for col in 0..cols_to_check {
-                if self.0[row][col] == Some(player)
-                    && self.0[row][col + 1] == Some(player)
-                    && self.0[row][col + 2] == Some(player)
-                    && self.0[row][col + 3] == Some(player)
-                {
-                    return true;
-                }
-            }
+    /// Returns the number of columns where moves are legal.
+    ///
+    /// A column is full exactly when its top slot is taken, which the board
+    /// keeps track of, so this doesn't inspect any slot.
+    #[must_use]
+    pub const fn legal_move_count(&self) -> usize {
+        let mut count = 0;
+        let mut col = 0;
+        while col < W {
+            count += self.1[col] as usize;
+            col += 1;
+        }
+        count
+    }
+
+    /// Returns the legal column closest to `from`, e.g., to move a cursor
+    /// away from a full column. Ties are broken toward the lower index.
+    ///
+    /// Returns `None` if no move is legal.
+    #[must_use]
+    pub fn nearest_legal(&self, from: usize) -> Option<usize> {
+        // the columns are emitted in ascending order and the first minimum
+        // wins
+        self.available_columns_iter()
+            .min_by_key(|col| col.abs_diff(from))
+    }
+
+    /// Returns the number of chips stacked above the slot `(row, col)` in the
+    /// same column.
+    #[must_use]
+    pub fn chips_above(&self, row: usize, col: usize) -> usize {
+        assert!(col < W);
+        self.0
+            .iter()
+            .skip(row + 1)
+            .filter(|r| r[col].is_some())
+            .count()
+    }
+
+    /// Returns the board mirrored along the vertical center axis.
+    #[must_use]
+    pub fn mirrored(&self) -> Self {
+        let mut board = self.clone();
+        for row in &mut board.0 {
+            row.reverse();
+        }
+        board.1 = Self::legal_mask(&board.0);
+        board
+    }
+
+    /// Lets all chips fall down to the lowest free slot of their column,
+    /// keeping their order within the column.
+    pub fn settle(&mut self) {
+        for col in 0..W {
+            let mut free_row = 0;
+            for row in 0..H {
+                if let Some(player) = self.0[row][col].take() {
+                    self.0[free_row][col] = Some(player);
+                    free_row += 1;
+                }
+            }
+        }
+        self.1 = Self::legal_mask(&self.0);
+    }
+
+    /// Copies the chips column by column into a board of a different size.
+    /// The chips of each column keep their order and settle at the bottom
+    /// of the new board, like after [`Self::settle`].
+    ///
+    /// # Errors
+    /// Fails with [`GameboardError::BoardTooSmall`] if the new board can't fit
+    /// a winning series, with [`GameboardError::InvalidColumn`] if a column
+    /// with chips doesn't exist on the new board, and with
+    /// [`GameboardError::ColumnFull`] if a column has more chips than the new
+    /// height.
+    ///
+    /// A reduced [`Self::scoring_height`] is kept, capped to the new height;
+    /// a board that scores with its full height scores with the full new
+    /// height as well.
+    pub fn resize<const W2: usize, const H2: usize>(
+        &self,
+    ) -> Result<Gameboard<W2, H2, K>, GameboardError> {
+        let mut board = Gameboard::<W2, H2, K>::try_new()?;
+        for col in 0..W {
+            for row in 0..H {
+                if let Some(player) = self.0[row][col] {
+                    board.insert_player_chip(col, player)?;
+                }
+            }
+        }
+        if self.2 < H {
+            board.2 = self.2.min(H2);
+        }
+        Ok(board)
+    }
+
+    /// Returns the board with the chips of both players exchanged, i.e., the
+    /// position from the opponent's perspective.
+    ///
+    /// [`evaluate_board`] scores the swapped board for a player like the
+    /// original board for the opponent.
+    #[must_use]
+    pub fn swapped(&self) -> Self {
+        let mut board = self.clone();
+        for slot in board.0.iter_mut().flatten() {
+            *slot = slot.map(Player::opponent);
+        }
+        board
+    }
+
+    /// Returns the number of slots whose content differs between the two
+    /// boards.
+    #[must_use]
+    pub fn hamming_distance(&self, other: &Self) -> usize {
+        self.0
+            .iter()
+            .flatten()
+            .zip(other.0.iter().flatten())
+            .filter(|(a, b)| a != b)
+            .count()
+    }
+
+    /// Returns the number of free slots on the board, i.e., the number of
+    /// moves until the board is full.
+    #[must_use]
+    pub fn remaining_moves(&self) -> usize {
+        self.0
+            .iter()
+            .flatten()
+            .filter(|slot| slot.is_none())
+            .count()
+    }
+
+    /// Returns the status of the game on this board.
+    ///
+    /// If both players have a series, which can't happen in a regular game,
+    /// [`Player::Player1`] is reported as winner.
+    #[must_use]
+    pub fn status(&self) -> GameStatus {
+        if self.check_for_winner(Player::Player1) {
+            GameStatus::Won(Player::Player1)
+        } else if self.check_for_winner(Player::Player2) {
+            GameStatus::Won(Player::Player2)
+        } else if self.gameover() {
+            GameStatus::Draw
+        } else {
+            GameStatus::InProgress
+        }
+    }
+
+    /// Plays the game until it is over with uniformly random legal moves,
+    /// starting with `to_move`, and returns the final status.
+    ///
+    /// `rng` is called once per move, e.g., with [`crate::Rng::next_u64`].
+    #[must_use]
+    pub fn random_rollout(&self, to_move: Player, rng: &mut impl FnMut() -> u64) -> GameStatus {
+        let mut board = self.clone();
+        let mut player = to_move;
+        loop {
+            let status = board.status();
+            if status != GameStatus::InProgress {
+                return status;
+            }
+
+            let index = (rng() % board.legal_move_count() as u64) as usize;
+            let col = board.available_columns_iter().nth(index).unwrap();
+            board.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+    }
+
+    /// Estimates the chance of `player`, who is to move, to win the game by
+    /// averaging over `rollouts` random games, see [`Self::random_rollout`].
+    /// Draws count as half a win.
+    ///
+    /// This is much cheaper than a search but only a rough indicator, as
+    /// random play misses most tactics. Returns `0.5` if `rollouts` is zero.
+    #[must_use]
+    pub fn estimate_win_rate(
+        &self,
+        player: Player,
+        rollouts: usize,
+        rng: &mut impl FnMut() -> u64,
+    ) -> f32 {
+        if rollouts == 0 {
+            return 0.5;
+        }
+
+        let points = (0..rollouts)
+            .map(|_| match self.random_rollout(player, rng) {
+                GameStatus::Won(winner) if winner == player => 2,
+                GameStatus::Draw => 1,
+                _ => 0,
+            })
+            .sum::<usize>();
+        points as f32 / (2 * rollouts) as f32
+    }
+
+    /// Returns the phase of the game, based on how much of the board is
+    /// filled.
+    #[must_use]
+    pub fn phase(&self) -> Phase {
+        let chips = W * H - self.remaining_moves();
+        if chips * 4 < W * H {
+            Phase::Opening
+        } else if chips * 4 > W * H * 3 {
+            Phase::Endgame
+        } else {
+            Phase::Midgame
+        }
+    }
+
+    /// Returns whether the game is over, i.e., there are no legal moves.
+    #[must_use]
+    pub const fn gameover(&self) -> bool {
+        self.legal_move_count() == 0
+    }
+
+    pub(crate) fn insert_player_chip(
+        &mut self,
+        column_index: usize,
+        player: Player,
+    ) -> Result<(), GameboardError> {
+        self.insert_returning_row(column_index, player).map(|_| ())
+    }
+
+    /// Inserts a chip of `player` into the given column and returns the row
+    /// index where it landed.
+    pub fn insert_returning_row(
+        &mut self,
+        column_index: usize,
+        player: Player,
+    ) -> Result<usize, GameboardError> {
+        if column_index >= W {
+            return Err(GameboardError::InvalidColumn);
+        }
+
+        let row_index = self
+            .next_slot_in_column(column_index)
+            .ok_or(GameboardError::ColumnFull)?;
+        self.0[row_index][column_index] = Some(player);
+        if row_index == H - 1 {
+            self.1[column_index] = false;
+        }
+        Ok(row_index)
+    }
+
+    /// Drops up to `count` chips of `player` into `column` and returns how
+    /// many were actually placed. Stops without error when the column is
+    /// full.
+    ///
+    /// # Errors
+    /// Fails with [`GameboardError::InvalidColumn`] if the column doesn't
+    /// exist.
+    pub fn drop_many(
+        &mut self,
+        column: usize,
+        player: Player,
+        count: usize,
+    ) -> Result<usize, GameboardError> {
+        if column >= W {
+            return Err(GameboardError::InvalidColumn);
+        }
+        let placed = count.min(self.free_slots_in_column(column));
+        for _ in 0..placed {
+            self.insert_returning_row(column, player)?;
+        }
+        Ok(placed)
+    }
+
+    /// Inserts the chips of all `moves` in order, or none of them if one of
+    /// the moves is illegal.
+    ///
+    /// # Errors
+    /// Returns the error of the first illegal move, after taking back the
+    /// moves before it.
+    pub fn try_apply_sequence(&mut self, moves: &[(usize, Player)]) -> Result<(), GameboardError> {
+        for (i, &(column, player)) in moves.iter().enumerate() {
+            if let Err(e) = self.insert_player_chip(column, player) {
+                for &(column, _) in moves[..i].iter().rev() {
+                    self.remove_top_chip(column);
+                }
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes the topmost chip of the given column and returns its owner,
+    /// i.e., takes back the last move in that column.
+    ///
+    /// # Errors
+    /// Fails with [`GameboardError::InvalidColumn`] if the column doesn't
+    /// exist and with [`GameboardError::NothingToUndo`] if it has no chips.
+    pub fn undo_move(&mut self, column_index: usize) -> Result<Player, GameboardError> {
+        if column_index >= W {
+            return Err(GameboardError::InvalidColumn);
+        }
+        self.remove_top_chip(column_index)
+            .ok_or(GameboardError::NothingToUndo)
+    }
+
+    /// Removes the topmost chip of the given column and returns its owner.
+    ///
+    /// Returns `None` if the column is empty.
+    pub(crate) fn remove_top_chip(&mut self, column_index: usize) -> Option<Player> {
+        let row_index = self
+            .next_slot_in_column(column_index)
+            .unwrap_or(H)
+            .checked_sub(1)?;
+        self.remove_chip_at(row_index, column_index)
+    }
+
+    /// Removes the chip at the given slot, which must be the topmost of its
+    /// column, e.g., as returned by [`Self::insert_returning_row`], and
+    /// returns its owner.
+    ///
+    /// All chip removals go through this function, as it keeps the legal
+    /// move mask in sync.
+    pub(crate) fn remove_chip_at(
+        &mut self,
+        row_index: usize,
+        column_index: usize,
+    ) -> Option<Player> {
+        debug_assert!(row_index + 1 == H || self.0[row_index + 1][column_index].is_none());
+        self.1[column_index] = true;
+        self.0[row_index][column_index].take()
+    }
+
+    fn check_for_winner_vertically(&self, player: Player) -> bool {
+        // check vertically
+        for col in 0..W {
+            let rows_to_check = H - K + 1;
+            for row in 0..rows_to_check {
+                if (0..K).all(|i| self.0[row + i][col] == Some(player)) {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    fn check_for_winner_horizontally(&self, player: Player) -> bool {
+        // check vertically
+        for row in 0..H {
+            let cols_to_check = W - K + 1;
+            for col in 0..cols_to_check {
+                if (0..K).all(|i| self.0[row][col + i] == Some(player)) {
+                    return true;
+                }
+            }
         }
 
         false
     }
 
     fn check_for_winner_diagonally(&self, player: Player) -> bool {
-        // check diagonally (`/`):
+        self.check_for_winner_diag_down(player) || self.check_for_winner_diag_up(player)
+    }
+
+    /// Checks the diagonals downwards to the right (`\`), see
+    /// [`Direction::DiagDown`].
+    fn check_for_winner_diag_down(&self, player: Player) -> bool {
+        // each series goes upwards to the left
         // -> iteration top-left to bottom-right
-        // -> diagonals going from bottom-left to top-right
         {
             // skip unneeded diagonals
-            let d_min = SERIES_LEN - 1;
-            let d_max = H + W - SERIES_LEN - 1;
+            let d_min = K - 1;
+            let d_max = H + W - K - 1;
 
             for d in d_min..=d_max {
                 let row_begin = d.saturating_sub(W - 1);
@@ -197,31 +1292,32 @@ impl<const W: usize, const H: usize> Gameboard<W, H> {
                 let d_len = row_end - row_begin + 1;
 
                 // Should not happen due to precondition
-                debug_assert!(d_len >= SERIES_LEN);
+                debug_assert!(d_len >= K);
 
                 // We iterate only as far as we can find a valid series
-                for row in row_begin..=(row_end + 1 - SERIES_LEN) {
+                for row in row_begin..=(row_end + 1 - K) {
                     let col = d - row;
 
-                    if self.0[row][col] == Some(player)
-                        && self.0[row + 1][col - 1] == Some(player)
-                        && self.0[row + 2][col - 2] == Some(player)
-                        && self.0[row + 3][col - 3] == Some(player)
-                    {
+                    if (0..K).all(|i| self.0[row + i][col - i] == Some(player)) {
                         return true;
                     }
                 }
             }
         }
 
-        // check diagonally (`\`):
+        false
+    }
+
+    /// Checks the diagonals upwards to the right (`/`), see
+    /// [`Direction::DiagUp`].
+    fn check_for_winner_diag_up(&self, player: Player) -> bool {
         // -> iteration bottom-left to top-right
         // -> diagonals going from bottom-left to top-right
         {
             // skip unneeded diagonals
             let d_min = -(W as isize - 1);
-            let d_low = d_min.max(4 - W as isize);
-            let d_high = H as isize - SERIES_LEN as isize;
+            let d_low = d_min.max(K as isize - W as isize);
+            let d_high = H as isize - K as isize;
 
             for d in d_low..=d_high {
                 // r in [max(0,k), min(H, W+k))
@@ -231,17 +1327,13 @@ impl<const W: usize, const H: usize> Gameboard<W, H> {
 
                 let d_len = row_end_exclusive.saturating_sub(row_begin);
                 // Should not happen due to precondition
-                debug_assert!(d_len >= SERIES_LEN);
+                debug_assert!(d_len >= K);
 
                 // We iterate only as far as we can find a valid series
-                for row in row_begin..(row_end_exclusive + 1 - SERIES_LEN) {
+                for row in row_begin..(row_end_exclusive + 1 - K) {
                     let col = (row as isize - d) as usize;
 
-                    if self.0[row][col] == Some(player)
-                        && self.0[row + 1][col + 1] == Some(player)
-                        && self.0[row + 2][col + 2] == Some(player)
-                        && self.0[row + 3][col + 3] == Some(player)
-                    {
+                    if (0..K).all(|i| self.0[row + i][col + i] == Some(player)) {
                         return true;
                     }
                 }
@@ -251,100 +1343,908 @@ impl<const W: usize, const H: usize> Gameboard<W, H> {
         false
     }
 
+    /// Calls `f` with the coordinates `(row, col)` and the slots of every
+    /// window of `K` consecutive slots in which a player could
+    /// connect, i.e., horizontally, vertically, and diagonally.
+    pub fn for_each_window(&self, mut f: impl FnMut([(usize, usize); K], [Cell; K])) {
+        // (row, col) directions: horizontal, vertical, diagonal `/`, diagonal `\`
+        const DIRECTIONS: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+        let len = K as isize;
+
+        for (d_row, d_col) in DIRECTIONS {
+            for row in 0..H as isize {
+                for col in 0..W as isize {
+                    let end_row = row + d_row * (len - 1);
+                    let end_col = col + d_col * (len - 1);
+                    if end_row >= self.2 as isize || end_col < 0 || end_col >= W as isize {
+                        continue;
+                    }
+
+                    let coords: [(usize, usize); K] = core::array::from_fn(|i| {
+                        let i = i as isize;
+                        ((row + d_row * i) as usize, (col + d_col * i) as usize)
+                    });
+                    let slots = coords.map(|(row, col)| self.0[row][col]);
+                    f(coords, slots);
+                }
+            }
+        }
+    }
+
+    /// Returns the `K` slots starting at `(row, col)` in direction
+    /// `dir`, or `None` if the window doesn't fit into the board.
+    #[must_use]
+    pub fn line_at(&self, row: usize, col: usize, dir: Direction) -> Option<[Cell; K]> {
+        let (d_row, d_col) = dir.delta();
+        let last = K as isize - 1;
+        let end_row = row.checked_add_signed(d_row * last)?;
+        let end_col = col.checked_add_signed(d_col * last)?;
+        if row >= H || col >= W || end_row >= H || end_col >= W {
+            return None;
+        }
+
+        Some(core::array::from_fn(|i| {
+            let i = i as isize;
+            self.0[(row as isize + d_row * i) as usize][(col as isize + d_col * i) as usize]
+        }))
+    }
+
+    /// Returns a copy of the board without the chips above the
+    /// [`Self::scoring_height`] that scores with its full height, or `None`
+    /// if all rows already count.
+    fn scoring_rows(&self) -> Option<Self> {
+        if self.2 == H {
+            return None;
+        }
+        let mut scoring = self.clone();
+        for row in &mut scoring.0[self.2..] {
+            *row = [None; W];
+        }
+        scoring.1 = Self::legal_mask(&scoring.0);
+        scoring.2 = H;
+        Some(scoring)
+    }
+
     /// Check if there is a winner.
+    ///
+    /// Chips above the [`Self::scoring_height`] don't count.
     #[must_use]
     pub fn check_for_winner(&self, player: Player) -> bool {
+        if let Some(scoring) = self.scoring_rows() {
+            return scoring.check_for_winner(player);
+        }
+
         self.check_for_winner_horizontally(player)
             || self.check_for_winner_vertically(player)
             || self.check_for_winner_diagonally(player)
     }
 
+    /// Returns the direction of the first winning series of `player` that is
+    /// found, checking in the same order as [`Self::check_for_winner`].
+    ///
+    /// Returns `None` if `player` has no winning series.
     #[must_use]
-    pub const fn width(&self) -> usize {
-        W
+    pub fn winning_direction(&self, player: Player) -> Option<Direction> {
+        if let Some(scoring) = self.scoring_rows() {
+            return scoring.winning_direction(player);
+        }
+
+        if self.check_for_winner_horizontally(player) {
+            Some(Direction::Horizontal)
+        } else if self.check_for_winner_vertically(player) {
+            Some(Direction::Vertical)
+        } else if self.check_for_winner_diag_down(player) {
+            Some(Direction::DiagDown)
+        } else if self.check_for_winner_diag_up(player) {
+            Some(Direction::DiagUp)
+        } else {
+            None
+        }
     }
 
+    /// Returns whether neither player can win anymore, because every window
+    /// of `K` slots holds chips of both players. The game ends in a draw no
+    /// matter how it continues.
     #[must_use]
-    pub const fn height(&self) -> usize {
-        H
+    pub fn is_dead_draw(&self) -> bool {
+        let mut open = false;
+        self.for_each_window(|_, slots| {
+            let p1 = slots.contains(&Some(Player::Player1));
+            let p2 = slots.contains(&Some(Player::Player2));
+            open |= !(p1 && p2);
+        });
+        !open
     }
-}
-
-#[derive(Copy, Clone, PartialOrd, PartialEq, Eq, Debug)]
-pub enum Player {
-    Player1,
-    Player2,
-}
 
-impl Player {
+    /// Returns whether any player has a winning series.
     #[must_use]
-    pub fn opponent(self) -> Self {
-        if self == Self::Player1 {
-            Self::Player2
-        } else {
-            Self::Player1
-        }
+    pub fn has_any_winner(&self) -> bool {
+        self.check_for_winner(Player::Player1) || self.check_for_winner(Player::Player2)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    extern crate std;
+    /// Simulates inserting a chip of `player` into `column` and returns the
+    /// coordinates `(row, col)` of the series that this move completes.
+    ///
+    /// Returns `None` if the move is illegal or doesn't win.
+    #[must_use]
+    pub fn winning_line_after(&self, column: usize, player: Player) -> Option<[(usize, usize); K]> {
+        let mut board = self.clone();
+        let row = board.insert_returning_row(column, player).ok()?;
 
-    use crate::{Gameboard, Player};
-    use std::vec::Vec;
+        let mut line = None;
+        board.for_each_window(|coords, slots| {
+            if line.is_none()
+                && coords.contains(&(row, column))
+                && slots.iter().all(|&slot| slot == Some(player))
+            {
+                line = Some(coords);
+            }
+        });
+        line
+    }
 
-    #[test]
-    fn test_next_slot_in_column() {
-        let mut board = Gameboard::<7, 6>::new();
-        assert_eq!(board.next_slot_in_column(0), Some(0));
+    /// Returns the coordinates `(row, col)` of the first series of `player`
+    /// that is found, e.g., to highlight it. The coordinates are sorted by
+    /// row and then by column, so diagonals always start at the bottom.
+    ///
+    /// Returns `None` if `player` has no winning series.
+    #[must_use]
+    pub fn winning_line(&self, player: Player) -> Option<[(usize, usize); K]> {
+        let mut line = None;
+        self.for_each_window(|mut coords, slots| {
+            if line.is_none() && slots.iter().all(|&slot| slot == Some(player)) {
+                coords.sort_unstable();
+                line = Some(coords);
+            }
+        });
+        line
+    }
 
-        for i in 0..board.height() - 1 {
-            board.0[i][0] = Some(Player::Player1);
-            assert_eq!(board.next_slot_in_column(0), Some(i + 1));
+    /// Renders the board top row first, with three characters per slot, and
+    /// wraps the slots `(row, col)` of `line` in brackets, e.g., to highlight
+    /// the series of the winner:
+    ///
+    /// ```text
+    ///  X  O [X]
+    /// ```
+    #[must_use]
+    pub fn to_string_highlighting(&self, line: &[(usize, usize)]) -> String {
+        let mut out = String::new();
+        for (row, cells) in self.0.iter().enumerate().rev() {
+            for (col, &cell) in cells.iter().enumerate() {
+                let (open, close) = if line.contains(&(row, col)) {
+                    ('[', ']')
+                } else {
+                    (' ', ' ')
+                };
+                out.push(open);
+                out.push(cell_symbol(cell));
+                out.push(close);
+            }
+            out.push('\n');
         }
+        out
+    }
 
-        board.0[board.height() - 1][0] = Some(Player::Player1);
-        assert_eq!(board.next_slot_in_column(0), None);
+    /// Renders the board top row first with emojis, e.g., for chat bots:
+    /// 🔴 for [`Player::Player1`], 🟡 for [`Player::Player2`], and ⚪ for
+    /// empty slots.
+    #[must_use]
+    pub fn to_emoji_string(&self) -> String {
+        self.to_string_with_glyphs(["⚪", "🔴", "🟡"])
     }
 
-    #[test]
-    fn test_free_slots_in_column() {
-        let mut board = Gameboard::<7, 6>::new();
-        assert_eq!(board.free_slots_in_column(0), 6);
+    /// Renders the board top row first with one glyph per slot, which may be
+    /// longer than a single character. The glyphs are given in the order
+    /// empty slot, [`Player::Player1`], [`Player::Player2`].
+    #[must_use]
+    pub fn to_string_with_glyphs(&self, glyphs: [&str; 3]) -> String {
+        let mut out = String::new();
+        for cells in self.0.iter().rev() {
+            for &cell in cells {
+                out.push_str(glyphs[cell.map_or(0, |player| usize::from(player.as_u8()))]);
+            }
+            out.push('\n');
+        }
+        out
+    }
 
-        for i in 0..board.height() {
-            board.0[i][0] = Some(Player::Player1);
-            assert_eq!(board.free_slots_in_column(0), 6 - i - 1);
+    /// Returns the empty slots that would complete a series of `player`,
+    /// regardless of whether they are playable yet.
+    pub(crate) fn threat_slots(&self, player: Player) -> [[bool; W]; H] {
+        let mut threats = [[false; W]; H];
+        self.for_each_window(|coords, slots| {
+            let own = slots.iter().filter(|&&slot| slot == Some(player)).count();
+            if own == K - 1
+                && let Some(&(row, col)) = coords
+                    .iter()
+                    .zip(slots)
+                    .find(|(_, slot)| slot.is_none())
+                    .map(|(coord, _)| coord)
+            {
+                threats[row][col] = true;
+            }
+        });
+        threats
+    }
+
+    /// Returns the slots `(row, col)` that would complete a series of the
+    /// opponent before, but no longer after `player` inserts a chip into
+    /// `column`.
+    ///
+    /// Returns an empty list if the move is illegal.
+    #[must_use]
+    pub fn threats_blocked_by(&self, column: usize, player: Player) -> Vec<(usize, usize)> {
+        let mut board = self.clone();
+        if board.insert_player_chip(column, player).is_err() {
+            return Vec::new();
         }
 
-        assert_eq!(board.free_slots_in_column(0), 0);
+        let before = self.threat_slots(player.opponent());
+        let after = board.threat_slots(player.opponent());
+        (0..H)
+            .flat_map(|row| (0..W).map(move |col| (row, col)))
+            .filter(|&(row, col)| before[row][col] && !after[row][col])
+            .collect()
     }
 
-    #[test]
-    fn find_winner_horizontally() {
-        {
-            let mut board = Gameboard::<7, 6>::new();
-            board.0[4][0] = Some(Player::Player1);
-            board.0[4][1] = Some(Player::Player1);
-            board.0[4][2] = Some(Player::Player1);
+    /// Returns the number of columns where `player` has two threats directly
+    /// on top of each other.
+    ///
+    /// Once the opponent blocks the lower threat, `player` wins with the
+    /// upper one, so these columns are very strong.
+    #[must_use]
+    pub fn stacked_threats(&self, player: Player) -> usize {
+        let threats = self.threat_slots(player);
+        (0..W)
+            .filter(|&col| (0..H - 1).any(|row| threats[row][col] && threats[row + 1][col]))
+            .count()
+    }
 
-            assert!(!board.check_for_winner_horizontally(Player::Player1));
-            assert!(!board.check_for_winner(Player::Player1));
-            assert!(!board.check_for_winner_horizontally(Player::Player2));
-            assert!(!board.check_for_winner(Player::Player2));
+    /// Returns whether inserting a chip of `player` into `column` wins the
+    /// game immediately.
+    pub(crate) fn is_winning_move(&self, column: usize, player: Player) -> bool {
+        let mut board = self.clone();
+        board.insert_player_chip(column, player).is_ok() && board.check_for_winner(player)
+    }
 
-            board.0[4][3] = Some(Player::Player1);
-            assert!(board.check_for_winner_horizontally(Player::Player1));
-            assert!(board.check_for_winner(Player::Player1));
-            assert!(!board.check_for_winner_horizontally(Player::Player2));
-            assert!(!board.check_for_winner(Player::Player2));
+    /// Returns the playable slots `(row, col)` where a chip of `player`
+    /// would win immediately.
+    #[must_use]
+    pub fn immediate_winning_cells(&self, player: Player) -> Vec<(usize, usize)> {
+        self.available_columns_iter()
+            .filter(|&col| self.is_winning_move(col, player))
+            .map(|col| (self.next_slot_in_column(col).unwrap(), col))
+            .collect()
+    }
+
+    /// Returns the number of legal columns where `player` wins immediately,
+    /// like [`Self::immediate_winning_cells`] but without allocating.
+    #[must_use]
+    pub fn immediate_win_count(&self, player: Player) -> usize {
+        self.available_columns_iter()
+            .filter(|&col| self.is_winning_move(col, player))
+            .count()
+    }
+
+    /// Returns for each column whether `player` would win immediately by
+    /// playing there, e.g., to highlight columns in a UI.
+    #[must_use]
+    pub fn columns_with_threats(&self, player: Player) -> [bool; W] {
+        let mut columns = [false; W];
+        for (_, col) in self.immediate_winning_cells(player) {
+            columns[col] = true;
         }
-        {
-            let mut board = Gameboard::<7, 6>::new();
-            board.insert_player_chip(0, Player::Player1).unwrap();
-            board.insert_player_chip(1, Player::Player1).unwrap();
-            board.insert_player_chip(2, Player::Player1).unwrap();
+        columns
+    }
+
+    /// Returns the number of immediate winning slots of `player` minus the
+    /// number of immediate winning slots of the opponent.
+    ///
+    /// This is a cheap measure of the tactical pressure, e.g., for move
+    /// ordering.
+    #[must_use]
+    pub fn threat_balance(&self, player: Player) -> i32 {
+        self.immediate_win_count(player) as i32 - self.immediate_win_count(player.opponent()) as i32
+    }
+
+    /// Returns the number of chips of `player` in the central column minus
+    /// the number of chips of the opponent there. For even widths, both
+    /// central columns count.
+    ///
+    /// Chips in the center take part in the most series, which makes this a
+    /// common term of heuristic evaluations.
+    #[must_use]
+    pub fn center_control(&self, player: Player) -> i32 {
+        let center = if W.is_multiple_of(2) {
+            W / 2 - 1..=W / 2
+        } else {
+            W / 2..=W / 2
+        };
+        self.0
+            .iter()
+            .flat_map(|row| &row[center.clone()])
+            .map(|&cell| match cell {
+                Some(p) if p == player => 1,
+                Some(_) => -1,
+                None => 0,
+            })
+            .sum()
+    }
+
+    /// Simulates inserting a chip of `player` into the legal `column` and
+    /// returns the slot `(row, col)` where it lands and the number of
+    /// immediate winning slots of `player` afterwards.
+    fn threats_after(&self, column: usize, player: Player) -> ((usize, usize), usize) {
+        let mut board = self.clone();
+        let row = board.insert_returning_row(column, player).unwrap();
+        ((row, column), board.immediate_win_count(player))
+    }
+
+    /// Returns the playable slots `(row, col)` that create two or more
+    /// immediate winning slots for `player` when claimed, i.e., a fork the
+    /// opponent can't block completely.
+    ///
+    /// Slots that win right away aren't included. Threats that already exist
+    /// are counted as well.
+    #[must_use]
+    pub fn fork_cells(&self, player: Player) -> Vec<(usize, usize)> {
+        self.available_columns_iter()
+            .filter(|&col| !self.is_winning_move(col, player))
+            .map(|col| self.threats_after(col, player))
+            .filter(|&(_, threats)| threats >= 2)
+            .map(|(cell, _)| cell)
+            .collect()
+    }
+
+    /// Returns the number of playable slots that create a fork for `player`,
+    /// like [`Self::fork_cells`] but without allocating.
+    #[must_use]
+    pub fn double_threat_count(&self, player: Player) -> usize {
+        self.available_columns_iter()
+            .filter(|&col| !self.is_winning_move(col, player))
+            .filter(|&col| self.threats_after(col, player).1 >= 2)
+            .count()
+    }
+
+    /// Returns the playable slot `(row, col)` that creates the most
+    /// immediate winning slots for `player` when claimed, e.g., a fork.
+    ///
+    /// Ties are broken towards the center column. Returns `None` if no
+    /// playable slot creates a winning slot at all.
+    #[must_use]
+    pub fn best_threat_cell(&self, player: Player) -> Option<(usize, usize)> {
+        self.available_columns_iter()
+            .map(|col| self.threats_after(col, player))
+            .filter(|&(_, threats)| threats > 0)
+            .min_by_key(|&((_, col), threats)| (core::cmp::Reverse(threats), col.abs_diff(W / 2)))
+            .map(|(cell, _)| cell)
+    }
+
+    /// Returns the columns where the opponent of `player` would win with
+    /// their next move, i.e., the columns `player` has to block.
+    #[must_use]
+    pub fn blocking_moves(&self, player: Player) -> Vec<usize> {
+        self.available_columns_iter()
+            .filter(|&col| self.is_winning_move(col, player.opponent()))
+            .collect()
+    }
+
+    /// Returns the columns where a chip of `player` enables an immediate
+    /// winning reply of the opponent, e.g., by filling the slot below an
+    /// opponent's threat.
+    #[must_use]
+    pub fn losing_moves(&self, player: Player) -> Vec<usize> {
+        self.available_columns_iter()
+            .filter(|&col| {
+                let mut board = self.clone();
+                board.insert_player_chip(col, player).unwrap();
+                !board.check_for_winner(player)
+                    && board
+                        .available_columns_iter()
+                        .any(|reply| board.is_winning_move(reply, player.opponent()))
+            })
+            .collect()
+    }
+
+    /// Returns whether every legal move of `player` enables an immediate
+    /// winning reply of the opponent, i.e., `player` is in zugzwang.
+    ///
+    /// Returns `false` if there are no legal moves.
+    #[must_use]
+    pub fn is_zugzwang(&self, player: Player) -> bool {
+        let legal_moves = self.legal_move_count();
+        legal_moves > 0 && self.losing_moves(player).len() == legal_moves
+    }
+
+    /// Returns whether `player` must block an immediate winning move of the
+    /// opponent to not lose the game.
+    #[must_use]
+    pub fn in_check(&self, player: Player) -> bool {
+        !self.blocking_moves(player).is_empty()
+    }
+
+    /// Returns whether inserting a chip of `player` into `column` leads to a
+    /// win in the next move of `player`, regardless of the opponent's reply.
+    fn is_two_move_win(&self, column: usize, player: Player) -> bool {
+        let mut board = self.clone();
+        if board.insert_player_chip(column, player).is_err() || board.gameover() {
+            return false;
+        }
+
+        board.available_columns_iter().all(|reply| {
+            let mut board = board.clone();
+            board.insert_player_chip(reply, player.opponent()).unwrap();
+            !board.check_for_winner(player.opponent())
+                && board
+                    .available_columns_iter()
+                    .any(|col| board.is_winning_move(col, player))
+        })
+    }
+
+    /// Returns whether `player`, who is to move, can force a win within
+    /// `plies` plies, counting the moves of both players.
+    ///
+    /// Unlike the MinMax search, this only tries to find a win and stops as
+    /// soon as the opponent has a defense, so it is a cheap tactical
+    /// indicator for small `plies`.
+    #[must_use]
+    pub fn can_win_within(&self, player: Player, plies: usize) -> bool {
+        if plies == 0 {
+            return false;
+        }
+
+        self.available_columns_iter().any(|col| {
+            let mut board = self.clone();
+            board.insert_player_chip(col, player).unwrap();
+            if board.check_for_winner(player) {
+                return true;
+            }
+            plies >= 3
+                && !board.gameover()
+                && board.available_columns_iter().all(|reply| {
+                    let mut board = board.clone();
+                    board.insert_player_chip(reply, player.opponent()).unwrap();
+                    !board.check_for_winner(player.opponent())
+                        && board.can_win_within(player, plies - 2)
+                })
+        })
+    }
+
+    /// Returns all columns that lead to a win for `player` within their next
+    /// two moves, paired with the distance to the win in plies.
+    ///
+    /// An immediate win has a distance of `1`, a win that the opponent can't
+    /// prevent with their reply a distance of `3`. The result is sorted by
+    /// ascending distance, so the fastest win comes first.
+    #[must_use]
+    pub fn winning_moves_by_speed(&self, player: Player) -> Vec<(usize, i32)> {
+        let mut moves = self
+            .available_columns_iter()
+            .filter_map(|col| {
+                if self.is_winning_move(col, player) {
+                    Some((col, 1))
+                } else if self.is_two_move_win(col, player) {
+                    Some((col, 3))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        moves.sort_by_key(|&(col, distance)| (distance, col));
+        moves
+    }
+
+    #[must_use]
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    #[must_use]
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    /// Returns the number of rows, counted from the bottom, that count for
+    /// wins. Equals [`Self::height`] unless the board was created by
+    /// [`Self::with_scoring_height`].
+    #[must_use]
+    pub const fn scoring_height(&self) -> usize {
+        self.2
+    }
+
+    /// Returns the chips as sorted `(row, col, player)` triples, where
+    /// `player` is `1` or `2`, e.g., as a map key for boards of any size.
+    ///
+    /// Unlike the transposition table, this doesn't canonicalize mirrored
+    /// boards. The key only covers the chips, i.e., boards that differ in
+    /// their [`Self::scoring_height`] share a key.
+    ///
+    /// # Panics
+    /// Panics if the board has more than 256 rows or columns.
+    #[must_use]
+    pub fn content_key(&self) -> Vec<(u8, u8, u8)> {
+        // iterating rows and columns in ascending order yields sorted triples
+        (0..H)
+            .flat_map(|row| (0..W).map(move |col| (row, col)))
+            .filter_map(|(row, col)| {
+                let player = self.0[row][col]?;
+                Some((
+                    u8::try_from(row).unwrap(),
+                    u8::try_from(col).unwrap(),
+                    player.as_u8(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Number of bits per slot in the compact `u128` encoding.
+    const BITS_PER_SLOT: usize = 2;
+
+    /// Packs the board into a single `u128` with two bits per slot, row by
+    /// row starting at the bottom left.
+    ///
+    /// Empty slots are encoded as `0b00`, [`Player::Player1`] as `0b01`, and
+    /// [`Player::Player2`] as `0b10`. Returns `None` if the board has more
+    /// than 64 slots and therefore doesn't fit.
+    ///
+    /// The [`Self::scoring_height`] isn't encoded.
+    #[must_use]
+    pub fn to_u128(&self) -> Option<u128> {
+        if W * H * Self::BITS_PER_SLOT > u128::BITS as usize {
+            return None;
+        }
+
+        let bits = self
+            .0
+            .iter()
+            .flatten()
+            .enumerate()
+            .fold(0, |bits, (i, slot)| {
+                let value: u128 = match slot {
+                    None => 0b00,
+                    Some(Player::Player1) => 0b01,
+                    Some(Player::Player2) => 0b10,
+                };
+                bits | (value << (i * Self::BITS_PER_SLOT))
+            });
+        Some(bits)
+    }
+
+    /// Unpacks a board encoded by [`Self::to_u128`]. The board scores with
+    /// its full height, as the encoding doesn't carry the
+    /// [`Self::scoring_height`].
+    ///
+    /// # Panics
+    /// Panics if the board doesn't fit into a `u128` or if `bits` contains
+    /// an invalid slot encoding.
+    #[must_use]
+    pub fn from_u128(bits: u128) -> Self {
+        assert!(W * H * Self::BITS_PER_SLOT <= u128::BITS as usize);
+
+        let mut board = Self::new();
+        for (i, slot) in board.0.iter_mut().flatten().enumerate() {
+            *slot = match (bits >> (i * Self::BITS_PER_SLOT)) & 0b11 {
+                0b00 => None,
+                0b01 => Some(Player::Player1),
+                0b10 => Some(Player::Player2),
+                _ => panic!("invalid slot encoding"),
+            };
+        }
+        board.1 = Self::legal_mask(&board.0);
+        board
+    }
+
+    /// Heuristically scores the position from the perspective of `player`
+    /// with [`evaluate_board`] and the default [`EvalWeights`]: open twos and
+    /// threes and chips in the center count positive, the same features of
+    /// the opponent negative.
+    ///
+    /// Terminal positions aren't taken into account, i.e., the caller should
+    /// check for a winner first.
+    #[must_use]
+    pub fn evaluate(&self, player: Player) -> i32 {
+        evaluate_board(self, player, &EvalWeights::default())
+    }
+
+    /// Returns how many maximal runs of consecutive chips of `player` there
+    /// are per length, e.g., for an analysis panel. The index is the length
+    /// of the run, up to `K`; longer runs count as `K`.
+    ///
+    /// Runs of two or more chips are counted once per direction. A chip that
+    /// isn't part of such a run in any direction counts as a run of length
+    /// `1`. Index `0` is always `0`.
+    #[must_use]
+    pub fn run_length_histogram(&self, player: Player) -> Vec<usize> {
+        let owned = |row: Option<usize>, col: Option<usize>| {
+            row.zip(col)
+                .filter(|&(row, col)| row < H && col < W)
+                .is_some_and(|(row, col)| self.0[row][col] == Some(player))
+        };
+
+        let mut histogram = alloc::vec![0; K + 1];
+        let mut in_run = [[false; W]; H];
+        for dir in [
+            Direction::Horizontal,
+            Direction::Vertical,
+            Direction::DiagUp,
+            Direction::DiagDown,
+        ] {
+            let (d_row, d_col) = dir.delta();
+            for row in 0..H {
+                for col in 0..W {
+                    // only start at the first chip of a run
+                    if !owned(Some(row), Some(col))
+                        || owned(
+                            row.checked_add_signed(-d_row),
+                            col.checked_add_signed(-d_col),
+                        )
+                    {
+                        continue;
+                    }
+
+                    let mut len = 1;
+                    while owned(
+                        row.checked_add_signed(d_row * len as isize),
+                        col.checked_add_signed(d_col * len as isize),
+                    ) {
+                        len += 1;
+                    }
+                    if len >= 2 {
+                        histogram[len.min(K)] += 1;
+                        for i in 0..len as isize {
+                            in_run[(row as isize + d_row * i) as usize]
+                                [(col as isize + d_col * i) as usize] = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        histogram[1] = (0..H)
+            .flat_map(|row| (0..W).map(move |col| (row, col)))
+            .filter(|&(row, col)| self.0[row][col] == Some(player) && !in_run[row][col])
+            .count();
+        histogram
+    }
+}
+
+/// Read-only view of a board of any size.
+///
+/// Boards of different sizes are different types; this trait allows to
+/// handle them together, e.g., as `&dyn BoardView` in a renderer.
+pub trait BoardView {
+    /// Number of columns.
+    fn width(&self) -> usize;
+    /// Number of rows.
+    fn height(&self) -> usize;
+    /// Content of the slot `(row, col)`, where row `0` is the bottom row.
+    ///
+    /// # Panics
+    /// Panics if the slot doesn't exist.
+    fn cell(&self, row: usize, col: usize) -> Cell;
+}
+
+impl<const W: usize, const H: usize, const K: usize> BoardView for Gameboard<W, H, K> {
+    fn width(&self) -> usize {
+        W
+    }
+
+    fn height(&self) -> usize {
+        H
+    }
+
+    fn cell(&self, row: usize, col: usize) -> Cell {
+        self.0[row][col]
+    }
+}
+
+/// Direction of a series of slots, see [`Gameboard::line_at`].
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Debug)]
+pub enum Direction {
+    /// To the right.
+    Horizontal,
+    /// Upwards.
+    Vertical,
+    /// Upwards to the right (`/`).
+    DiagUp,
+    /// Downwards to the right (`\`).
+    DiagDown,
+}
+
+impl Direction {
+    /// Returns the step `(row, col)` from one slot to the next.
+    const fn delta(self) -> (isize, isize) {
+        match self {
+            Self::Horizontal => (0, 1),
+            Self::Vertical => (1, 0),
+            Self::DiagUp => (1, 1),
+            Self::DiagDown => (-1, 1),
+        }
+    }
+}
+
+/// Status of a game.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Debug)]
+pub enum GameStatus {
+    /// There are legal moves left and nobody has won yet.
+    InProgress,
+    /// The player has connected a series.
+    Won(Player),
+    /// The board is full and nobody has won.
+    Draw,
+}
+
+impl GameStatus {
+    /// Returns a short code of the status for logs: `-` (in progress), `1`
+    /// or `2` (won by that player), or `D` (draw).
+    #[must_use]
+    pub const fn code(self) -> char {
+        match self {
+            Self::InProgress => '-',
+            Self::Won(Player::Player1) => '1',
+            Self::Won(Player::Player2) => '2',
+            Self::Draw => 'D',
+        }
+    }
+}
+
+/// Phase of a game, based on how much of the board is filled.
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Debug)]
+pub enum Phase {
+    /// Less than 25% of the slots are taken.
+    Opening,
+    /// Between 25% and 75% of the slots are taken.
+    Midgame,
+    /// More than 75% of the slots are taken.
+    Endgame,
+}
+
+/// A player. The discriminants are stable, see [`Self::as_u8`].
+#[derive(Copy, Clone, PartialOrd, Ord, PartialEq, Eq, Debug)]
+#[repr(u8)]
+pub enum Player {
+    Player1 = 1,
+    Player2 = 2,
+}
+
+impl Player {
+    #[must_use]
+    pub fn opponent(self) -> Self {
+        if self == Self::Player1 {
+            Self::Player2
+        } else {
+            Self::Player1
+        }
+    }
+
+    /// Returns the number of the player, `1` or `2`, e.g., for byte
+    /// serialization. `0` is free to encode an empty slot.
+    #[must_use]
+    pub const fn as_u8(self) -> u8 {
+        self as u8
+    }
+
+    /// Inverse of [`Self::as_u8`]. Returns `None` for other values.
+    #[must_use]
+    pub const fn try_from_u8(value: u8) -> Option<Self> {
+        match value {
+            1 => Some(Self::Player1),
+            2 => Some(Self::Player2),
+            _ => None,
+        }
+    }
+
+    /// Parses a player from user input.
+    ///
+    /// Accepts `1`/`2`, `X`/`O` (the symbols of the CLI), and
+    /// `player1`/`player2`, all case-insensitive. Surrounding whitespace is
+    /// ignored.
+    // Returning an `Option` is more convenient for the CLI than `FromStr`.
+    #[allow(clippy::should_implement_trait)]
+    #[must_use]
+    pub fn from_str(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if ["1", "x", "player1"]
+            .iter()
+            .any(|name| s.eq_ignore_ascii_case(name))
+        {
+            Some(Self::Player1)
+        } else if ["2", "o", "player2"]
+            .iter()
+            .any(|name| s.eq_ignore_ascii_case(name))
+        {
+            Some(Self::Player2)
+        } else {
+            None
+        }
+    }
+}
+
+/// Content of a single slot of the board: a chip of a player or nothing.
+pub type Cell = Option<Player>;
+
+/// Returns the symbol the CLI uses to draw a slot: `X` for
+/// [`Player::Player1`], `O` for [`Player::Player2`], and a space for an
+/// empty slot.
+#[must_use]
+pub const fn cell_symbol(cell: Cell) -> char {
+    match cell {
+        None => ' ',
+        Some(Player::Player1) => 'X',
+        Some(Player::Player2) => 'O',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use crate::{
+        BoardView, Direction, Game, GameStatus, Gameboard, GameboardError, MIDGAME_MOVES,
+        MoveLineError, Phase, Player, PlayerStats, ReplayError, Rng, cell_symbol, collect_stats,
+        midgame_positions, parse_and_validate_move, replay_up_to, validate_moves,
+    };
+    use core::error::Error;
+    use core::fmt;
+    use std::boxed::Box;
+    use std::string::String;
+    use std::string::ToString;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_next_slot_in_column() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.next_slot_in_column(0), Some(0));
+
+        for i in 0..board.height() - 1 {
+            board.set_cell(i, 0, Some(Player::Player1)).unwrap();
+            assert_eq!(board.next_slot_in_column(0), Some(i + 1));
+        }
+
+        board
+            .set_cell(board.height() - 1, 0, Some(Player::Player1))
+            .unwrap();
+        assert_eq!(board.next_slot_in_column(0), None);
+    }
+
+    #[test]
+    fn test_free_slots_in_column() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.free_slots_in_column(0), 6);
+
+        for i in 0..board.height() {
+            board.set_cell(i, 0, Some(Player::Player1)).unwrap();
+            assert_eq!(board.free_slots_in_column(0), 6 - i - 1);
+        }
+
+        assert_eq!(board.free_slots_in_column(0), 0);
+    }
+
+    #[test]
+    fn find_winner_horizontally() {
+        {
+            let mut board = Gameboard::<7, 6>::new();
+            board.set_cell(4, 0, Some(Player::Player1)).unwrap();
+            board.set_cell(4, 1, Some(Player::Player1)).unwrap();
+            board.set_cell(4, 2, Some(Player::Player1)).unwrap();
+
+            assert!(!board.check_for_winner_horizontally(Player::Player1));
+            assert!(!board.check_for_winner(Player::Player1));
+            assert!(!board.check_for_winner_horizontally(Player::Player2));
+            assert!(!board.check_for_winner(Player::Player2));
+
+            board.set_cell(4, 3, Some(Player::Player1)).unwrap();
+            assert!(board.check_for_winner_horizontally(Player::Player1));
+            assert!(board.check_for_winner(Player::Player1));
+            assert!(!board.check_for_winner_horizontally(Player::Player2));
+            assert!(!board.check_for_winner(Player::Player2));
+        }
+        {
+            let mut board = Gameboard::<7, 6>::new();
+            board.insert_player_chip(0, Player::Player1).unwrap();
+            board.insert_player_chip(1, Player::Player1).unwrap();
+            board.insert_player_chip(2, Player::Player1).unwrap();
             board.insert_player_chip(3, Player::Player1).unwrap();
             assert!(board.check_for_winner_horizontally(Player::Player1));
             assert!(board.check_for_winner(Player::Player1));
@@ -368,16 +2268,16 @@ mod tests {
     fn find_winner_vertically() {
         {
             let mut board = Gameboard::<7, 6>::new();
-            board.0[0][5] = Some(Player::Player1);
-            board.0[1][5] = Some(Player::Player1);
-            board.0[2][5] = Some(Player::Player1);
+            board.set_cell(0, 5, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 5, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 5, Some(Player::Player1)).unwrap();
 
             assert!(!board.check_for_winner_vertically(Player::Player1));
             assert!(!board.check_for_winner(Player::Player1));
             assert!(!board.check_for_winner_vertically(Player::Player2));
             assert!(!board.check_for_winner(Player::Player2));
 
-            board.0[3][5] = Some(Player::Player1);
+            board.set_cell(3, 5, Some(Player::Player1)).unwrap();
             assert!(board.check_for_winner_vertically(Player::Player1));
             assert!(board.check_for_winner(Player::Player1));
             assert!(!board.check_for_winner_vertically(Player::Player2));
@@ -414,73 +2314,212 @@ mod tests {
         // direction=\, winner=no
         {
             let mut board = Gameboard::<7, 6>::new();
-            board.0[0][0] = Some(Player::Player1);
-            board.0[1][1] = Some(Player::Player1);
-            board.0[2][2] = Some(Player::Player1);
+            board.set_cell(0, 0, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 1, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 2, Some(Player::Player1)).unwrap();
 
             assert!(!board.check_for_winner(Player::Player1));
 
-            board.0[4][3] = Some(Player::Player1);
+            board.set_cell(4, 3, Some(Player::Player1)).unwrap();
             assert!(!board.check_for_winner(Player::Player1));
         }
         // direction=\, winner=yes
         {
             let mut board = Gameboard::<7, 6>::new();
-            board.0[0][0] = Some(Player::Player1);
-            board.0[1][1] = Some(Player::Player1);
-            board.0[2][2] = Some(Player::Player1);
+            board.set_cell(0, 0, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 1, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 2, Some(Player::Player1)).unwrap();
 
             assert!(!board.check_for_winner(Player::Player1));
 
-            board.0[3][3] = Some(Player::Player1);
+            board.set_cell(3, 3, Some(Player::Player1)).unwrap();
             assert!(board.check_for_winner(Player::Player1));
         }
         // direction=\, winner=yes
         {
             let mut board = Gameboard::<7, 6>::new();
-            board.0[0][3] = Some(Player::Player1);
-            board.0[1][4] = Some(Player::Player1);
-            board.0[2][5] = Some(Player::Player1);
+            board.set_cell(0, 3, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 4, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 5, Some(Player::Player1)).unwrap();
 
             assert!(!board.check_for_winner(Player::Player1));
 
-            board.0[3][6] = Some(Player::Player1);
+            board.set_cell(3, 6, Some(Player::Player1)).unwrap();
             assert!(board.check_for_winner(Player::Player1));
         }
         // direction=/, winner=yes
         {
             let mut board = Gameboard::<7, 6>::new();
-            board.0[4][3] = Some(Player::Player1);
-            board.0[3][4] = Some(Player::Player1);
-            board.0[2][5] = Some(Player::Player1);
+            board.set_cell(4, 3, Some(Player::Player1)).unwrap();
+            board.set_cell(3, 4, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 5, Some(Player::Player1)).unwrap();
 
             assert!(!board.check_for_winner(Player::Player1));
 
-            board.0[1][6] = Some(Player::Player1);
+            board.set_cell(1, 6, Some(Player::Player1)).unwrap();
             assert!(board.check_for_winner(Player::Player1));
         }
         // direction=\, winner=yes
         {
             let mut board = Gameboard::<4, 4>::new();
-            board.0[0][0] = Some(Player::Player1);
-            board.0[1][1] = Some(Player::Player1);
-            board.0[2][2] = Some(Player::Player1);
-            board.0[3][3] = Some(Player::Player1);
+            board.set_cell(0, 0, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 1, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(3, 3, Some(Player::Player1)).unwrap();
             assert!(board.check_for_winner_diagonally(Player::Player1));
             assert!(board.check_for_winner(Player::Player1));
         }
         // direction=/, winner=yes
         {
             let mut board = Gameboard::<4, 4>::new();
-            board.0[0][3] = Some(Player::Player1);
-            board.0[1][2] = Some(Player::Player1);
-            board.0[2][1] = Some(Player::Player1);
-            board.0[3][0] = Some(Player::Player1);
+            board.set_cell(0, 3, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 1, Some(Player::Player1)).unwrap();
+            board.set_cell(3, 0, Some(Player::Player1)).unwrap();
             assert!(board.check_for_winner_diagonally(Player::Player1));
             assert!(board.check_for_winner(Player::Player1));
         }
     }
 
+    #[test]
+    fn test_custom_series_len() {
+        let line = |cells: [(usize, usize); 3]| {
+            let mut board = Gameboard::<5, 5, 3>::new();
+            for (row, col) in cells {
+                board.set_cell(row, col, Some(Player::Player1)).unwrap();
+            }
+            board
+        };
+
+        for (cells, dir) in [
+            ([(4, 2), (4, 3), (4, 4)], Direction::Horizontal),
+            ([(0, 0), (1, 0), (2, 0)], Direction::Vertical),
+            ([(2, 2), (3, 3), (4, 4)], Direction::DiagUp),
+            ([(2, 2), (1, 3), (0, 4)], Direction::DiagDown),
+        ] {
+            let board = line(cells);
+            assert!(board.check_for_winner(Player::Player1));
+            assert_eq!(board.winning_direction(Player::Player1), Some(dir));
+            let mut sorted = cells;
+            sorted.sort_unstable();
+            assert_eq!(board.winning_line(Player::Player1), Some(sorted));
+        }
+        assert!(!line([(0, 0), (0, 1), (1, 3)]).check_for_winner(Player::Player1));
+
+        // three in a row doesn't win with the default series length
+        let mut board = Gameboard::<5, 5>::new();
+        let mut short = Gameboard::<5, 5, 3>::new();
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player2).unwrap();
+            short.insert_player_chip(col, Player::Player2).unwrap();
+        }
+        assert!(!board.check_for_winner(Player::Player2));
+        assert!(short.check_for_winner(Player::Player2));
+
+        let mut windows = 0;
+        short.for_each_window(|_, _| windows += 1);
+        // 15 horizontal, 15 vertical, 9 per diagonal direction
+        assert_eq!(windows, 48);
+        assert_eq!(
+            Gameboard::<5, 2, 3>::try_new(),
+            Err(GameboardError::BoardTooSmall {
+                width: 5,
+                height: 2,
+                required: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_winning_line() {
+        let line = |cells: [(usize, usize); 4]| {
+            let mut board = Gameboard::<7, 6>::new();
+            for (row, col) in cells {
+                board.set_cell(row, col, Some(Player::Player1)).unwrap();
+            }
+            board.winning_line(Player::Player1)
+        };
+
+        assert_eq!(Gameboard::<7, 6>::new().winning_line(Player::Player1), None);
+        assert_eq!(
+            line([(5, 6), (5, 4), (5, 5), (5, 3)]),
+            Some([(5, 3), (5, 4), (5, 5), (5, 6)])
+        );
+        assert_eq!(
+            line([(2, 0), (3, 0), (4, 0), (5, 0)]),
+            Some([(2, 0), (3, 0), (4, 0), (5, 0)])
+        );
+        // `/` from the bottom left and into the top right corner
+        assert_eq!(
+            line([(0, 0), (1, 1), (2, 2), (3, 3)]),
+            Some([(0, 0), (1, 1), (2, 2), (3, 3)])
+        );
+        assert_eq!(
+            line([(2, 3), (3, 4), (4, 5), (5, 6)]),
+            Some([(2, 3), (3, 4), (4, 5), (5, 6)])
+        );
+        // `\` from the top left and into the bottom right corner
+        assert_eq!(
+            line([(5, 0), (4, 1), (3, 2), (2, 3)]),
+            Some([(2, 3), (3, 2), (4, 1), (5, 0)])
+        );
+        assert_eq!(
+            line([(3, 3), (2, 4), (1, 5), (0, 6)]),
+            Some([(0, 6), (1, 5), (2, 4), (3, 3)])
+        );
+
+        let mut board = Gameboard::<7, 6>::new();
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player2).unwrap();
+        }
+        assert_eq!(board.winning_line(Player::Player2), None);
+        board.insert_player_chip(3, Player::Player2).unwrap();
+        assert_eq!(
+            board.winning_line(Player::Player2),
+            Some([(0, 0), (0, 1), (0, 2), (0, 3)])
+        );
+        assert_eq!(board.winning_line(Player::Player1), None);
+    }
+
+    #[test]
+    fn test_winning_direction() {
+        let line = |cells: [(usize, usize); 4]| {
+            let mut board = Gameboard::<7, 6>::new();
+            for (row, col) in cells {
+                board.set_cell(row, col, Some(Player::Player2)).unwrap();
+            }
+            board
+        };
+
+        let board = line([(2, 1), (2, 2), (2, 3), (2, 4)]);
+        assert_eq!(
+            board.winning_direction(Player::Player2),
+            Some(Direction::Horizontal)
+        );
+        assert_eq!(board.winning_direction(Player::Player1), None);
+        assert_eq!(
+            line([(1, 6), (2, 6), (3, 6), (4, 6)]).winning_direction(Player::Player2),
+            Some(Direction::Vertical)
+        );
+        assert_eq!(
+            line([(0, 0), (1, 1), (2, 2), (3, 3)]).winning_direction(Player::Player2),
+            Some(Direction::DiagUp)
+        );
+        assert_eq!(
+            line([(5, 2), (4, 3), (3, 4), (2, 5)]).winning_direction(Player::Player2),
+            Some(Direction::DiagDown)
+        );
+
+        // three in a row and chips above the scoring height don't win
+        let board = line([(0, 0), (1, 1), (2, 2), (5, 5)]);
+        assert_eq!(board.winning_direction(Player::Player2), None);
+        let mut board = Gameboard::<7, 6>::with_scoring_height(4).unwrap();
+        board.insert_player_chip(6, Player::Player1).unwrap();
+        board.drop_many(6, Player::Player2, 4).unwrap();
+        assert_eq!(board.winning_direction(Player::Player2), None);
+        assert!(!board.check_for_winner(Player::Player2));
+    }
+
     #[test]
     fn test_legal_moves_iter() {
         {
@@ -526,20 +2565,20 @@ mod tests {
         }
         {
             let mut board = Gameboard::<4, 4>::new();
-            board.0[0][0] = Some(Player::Player2);
-            board.0[0][1] = Some(Player::Player2);
-            board.0[0][2] = Some(Player::Player1);
-            board.0[0][3] = Some(Player::Player2);
-            board.0[1][0] = Some(Player::Player1);
-            board.0[1][1] = Some(Player::Player1);
-            board.0[1][2] = Some(Player::Player2);
-            board.0[1][3] = Some(Player::Player1);
-            board.0[2][0] = Some(Player::Player2);
-            board.0[2][1] = Some(Player::Player1);
-            board.0[2][2] = Some(Player::Player1);
-            board.0[2][3] = Some(Player::Player2);
-            board.0[3][0] = Some(Player::Player1);
-            board.0[3][1] = Some(Player::Player2);
+            board.set_cell(0, 0, Some(Player::Player2)).unwrap();
+            board.set_cell(0, 1, Some(Player::Player2)).unwrap();
+            board.set_cell(0, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(0, 3, Some(Player::Player2)).unwrap();
+            board.set_cell(1, 0, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 1, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 2, Some(Player::Player2)).unwrap();
+            board.set_cell(1, 3, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 0, Some(Player::Player2)).unwrap();
+            board.set_cell(2, 1, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 3, Some(Player::Player2)).unwrap();
+            board.set_cell(3, 0, Some(Player::Player1)).unwrap();
+            board.set_cell(3, 1, Some(Player::Player2)).unwrap();
 
             assert_eq!(
                 board
@@ -548,18 +2587,18 @@ mod tests {
                     .as_slice(),
                 &[2, 3]
             );
-            board.0[3][2] = Some(Player::Player1);
-            board.0[3][3] = Some(Player::Player2);
+            board.set_cell(3, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(3, 3, Some(Player::Player2)).unwrap();
             assert!(board.gameover());
         }
         {
             let mut board = Gameboard::<4, 4>::new();
-            board.0[0][0] = Some(Player::Player2);
-            board.0[0][2] = Some(Player::Player1);
-            board.0[1][0] = Some(Player::Player2);
-            board.0[1][2] = Some(Player::Player1);
-            board.0[2][2] = Some(Player::Player1);
-            board.0[3][2] = Some(Player::Player1);
+            board.set_cell(0, 0, Some(Player::Player2)).unwrap();
+            board.set_cell(0, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(1, 0, Some(Player::Player2)).unwrap();
+            board.set_cell(1, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(2, 2, Some(Player::Player1)).unwrap();
+            board.set_cell(3, 2, Some(Player::Player1)).unwrap();
 
             assert_eq!(
                 board
@@ -570,4 +2609,1743 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_rows_bottom_up_top_down() {
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+
+        let bottom_up = board.rows_bottom_up();
+        let top_down = board.rows_top_down();
+        assert_eq!(bottom_up.as_slice(), board.board());
+        assert_eq!(top_down[0], board.board()[3]);
+        assert_eq!(top_down[0][0], Some(Player::Player2));
+        assert_eq!(bottom_up[0][0], Some(Player::Player1));
+    }
+
+    #[test]
+    fn test_logical_cell() {
+        let mut board = Gameboard::<4, 5>::new();
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        for _ in 0..4 {
+            board.insert_player_chip(2, Player::Player2).unwrap();
+        }
+        board.insert_player_chip(2, Player::Player1).unwrap();
+
+        // display row 0 is the internal top row
+        assert_eq!(board.logical_cell(0, 2), Some(Player::Player1));
+        assert_eq!(board.logical_cell(0, 2), board.board()[4][2]);
+        assert_eq!(board.logical_cell(1, 2), Some(Player::Player2));
+        assert_eq!(board.logical_cell(4, 1), Some(Player::Player1));
+        assert_eq!(board.logical_cell(3, 1), None);
+        for (display_row, row) in board.rows_top_down().iter().enumerate() {
+            for (col, &cell) in row.iter().enumerate() {
+                assert_eq!(board.logical_cell(display_row, col), cell);
+            }
+        }
+
+        assert_eq!(board.logical_cell(5, 1), None);
+        assert_eq!(board.logical_cell(0, 4), None);
+    }
+
+    #[test]
+    fn test_legal_move_count() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.legal_move_count(), 7);
+
+        for (i, col) in [3, 3, 2, 4, 3, 3, 3, 3, 0, 6, 6, 6, 6, 6, 6]
+            .into_iter()
+            .enumerate()
+        {
+            let player = if i % 2 == 0 {
+                Player::Player1
+            } else {
+                Player::Player2
+            };
+            board.insert_player_chip(col, player).unwrap();
+            assert_eq!(
+                board.legal_move_count(),
+                board.available_columns_iter().count()
+            );
+        }
+        assert_eq!(board.legal_move_count(), 5);
+
+        for col in 0..board.width() {
+            while board.insert_player_chip(col, Player::Player1).is_ok() {}
+        }
+        assert_eq!(board.legal_move_count(), 0);
+        assert!(board.gameover());
+
+        // boards of any width keep track of their legal moves
+        let mut wide = Gameboard::<70, 4>::try_new().unwrap();
+        assert_eq!(wide.legal_move_count(), 70);
+        wide.drop_many(69, Player::Player1, 4).unwrap();
+        assert_eq!(wide.legal_move_count(), 69);
+        assert_eq!(wide.available_columns_iter().last(), Some(68));
+    }
+
+    #[test]
+    fn test_player_from_str() {
+        for s in ["1", "x", "X", "player1", "Player1", "PLAYER1", " x\n"] {
+            assert_eq!(Player::from_str(s), Some(Player::Player1), "{s}");
+        }
+        for s in ["2", "o", "O", "player2", "Player2", "PLAYER2", " o\n"] {
+            assert_eq!(Player::from_str(s), Some(Player::Player2), "{s}");
+        }
+        for s in ["", "0", "3", "0x", "player", "player3", "xo", "foo"] {
+            assert_eq!(Player::from_str(s), None, "{s}");
+        }
+    }
+
+    #[test]
+    fn test_winning_moves_by_speed() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert!(board.winning_moves_by_speed(Player::Player1).is_empty());
+
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+
+        // Playing column 3 creates two threats at once (columns 0 and 4)
+        assert_eq!(
+            board.winning_moves_by_speed(Player::Player1).as_slice(),
+            &[(3, 3)]
+        );
+
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        let moves = board.winning_moves_by_speed(Player::Player1);
+        assert_eq!(moves[0].1, 1);
+        assert!(moves[0].0 == 0 || moves[0].0 == 4);
+        assert_eq!(moves[1].1, 1);
+        assert!(moves.iter().any(|&(_, distance)| distance == 3));
+        assert!(moves.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert!(board.winning_moves_by_speed(Player::Player2).is_empty());
+    }
+
+    #[test]
+    fn test_chips_above() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.chips_above(0, 2), 0);
+
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(2, Player::Player2).unwrap();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+
+        assert_eq!(board.chips_above(0, 2), 2);
+        assert_eq!(board.chips_above(1, 2), 1);
+        assert_eq!(board.chips_above(2, 2), 0);
+        assert_eq!(board.chips_above(5, 2), 0);
+        assert_eq!(board.chips_above(0, 3), 0);
+    }
+
+    #[test]
+    fn test_in_check() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(2, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player2).unwrap();
+        assert!(!board.in_check(Player::Player1));
+        assert!(board.blocking_moves(Player::Player1).is_empty());
+
+        board.insert_player_chip(4, Player::Player2).unwrap();
+        assert!(board.in_check(Player::Player1));
+        assert!(!board.in_check(Player::Player2));
+        assert_eq!(board.blocking_moves(Player::Player1).as_slice(), &[1, 5]);
+
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        board.insert_player_chip(5, Player::Player1).unwrap();
+        assert!(!board.in_check(Player::Player1));
+    }
+
+    #[test]
+    fn test_u128_round_trip() {
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+        let mut next = || rng.next_u64() as usize;
+
+        for _ in 0..100 {
+            let mut board = Gameboard::<7, 6>::new();
+            let mut player = Player::Player1;
+            for _ in 0..next() % 43 {
+                let cols = board.available_columns_iter().collect::<Vec<_>>();
+                board
+                    .insert_player_chip(cols[next() % cols.len()], player)
+                    .unwrap();
+                player = player.opponent();
+            }
+
+            let bits = board.to_u128().unwrap();
+            assert_eq!(Gameboard::<7, 6>::from_u128(bits), board);
+        }
+
+        assert_eq!(Gameboard::<7, 6>::new().to_u128(), Some(0));
+        assert_eq!(Gameboard::<8, 8>::new().to_u128(), Some(0));
+        assert_eq!(Gameboard::<9, 8>::new().to_u128(), None);
+    }
+
+    #[test]
+    fn test_estimate_win_rate() {
+        let mut rng = Rng::new(7);
+        let mut next = || rng.next_u64();
+
+        // player 1 has a vertical three in every column but the last one
+        let mut board = Gameboard::<7, 6>::new();
+        for col in 0..6 {
+            board.drop_many(col, Player::Player1, 3).unwrap();
+        }
+        board.drop_many(6, Player::Player2, 3).unwrap();
+        let rate = board.estimate_win_rate(Player::Player1, 200, &mut next);
+        assert!(rate > 0.8, "rate = {rate}");
+        assert!(board.estimate_win_rate(Player::Player2, 200, &mut next) < 0.5);
+
+        // decided games
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        assert_eq!(board.estimate_win_rate(Player::Player1, 10, &mut next), 1.0);
+        assert_eq!(board.estimate_win_rate(Player::Player2, 10, &mut next), 0.0);
+        assert_eq!(board.estimate_win_rate(Player::Player2, 0, &mut next), 0.5);
+        assert_eq!(
+            board.random_rollout(Player::Player2, &mut next),
+            GameStatus::Won(Player::Player1)
+        );
+    }
+
+    #[test]
+    fn test_moves_until_full() {
+        let mut game = Game::<4, 4>::new();
+        assert_eq!(game.moves_until_full(), 16);
+        assert!(!game.is_last_move());
+
+        let mut player = Player::Player1;
+        for col in [0, 1, 2, 3, 0, 1, 2, 3, 0, 1, 2, 3, 0, 1] {
+            game.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+        assert_eq!(game.moves_until_full(), 2);
+        assert!(!game.is_last_move());
+
+        game.insert_player_chip(2, player).unwrap();
+        assert_eq!(game.moves_until_full(), 1);
+        assert!(game.is_last_move());
+
+        game.insert_player_chip(3, player.opponent()).unwrap();
+        assert_eq!(game.moves_until_full(), 0);
+        assert!(!game.is_last_move());
+        assert!(game.board().gameover());
+    }
+
+    #[test]
+    fn test_hamming_distance() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(5, Player::Player2).unwrap();
+        assert_eq!(board.hamming_distance(&board), 0);
+
+        let empty = Gameboard::<7, 6>::new();
+        assert_eq!(board.hamming_distance(&empty), 4);
+        assert_eq!(empty.hamming_distance(&board), 4);
+
+        let mut mirror = board.clone();
+        for row in &mut mirror.0 {
+            row.reverse();
+        }
+        // column 3 is the center and maps onto itself
+        assert_eq!(board.hamming_distance(&mirror), 6);
+    }
+
+    #[test]
+    fn test_parse_and_validate_move() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(parse_and_validate_move("1", &board), Ok(0));
+        assert_eq!(parse_and_validate_move(" 7\n", &board), Ok(6));
+
+        assert_eq!(
+            parse_and_validate_move("", &board),
+            Err(GameboardError::InvalidInput)
+        );
+        assert_eq!(
+            parse_and_validate_move("abc", &board),
+            Err(GameboardError::InvalidInput)
+        );
+        assert_eq!(
+            parse_and_validate_move("-1", &board),
+            Err(GameboardError::InvalidInput)
+        );
+
+        assert_eq!(
+            parse_and_validate_move("0", &board),
+            Err(GameboardError::InvalidColumn)
+        );
+        assert_eq!(
+            parse_and_validate_move("8", &board),
+            Err(GameboardError::InvalidColumn)
+        );
+
+        for _ in 0..board.height() {
+            board.insert_player_chip(2, Player::Player1).unwrap();
+        }
+        assert_eq!(
+            parse_and_validate_move("3", &board),
+            Err(GameboardError::ColumnFull)
+        );
+    }
+
+    #[test]
+    fn test_for_each_window() {
+        let mut count = 0;
+        Gameboard::<7, 6>::new().for_each_window(|_, _| count += 1);
+        // 24 horizontal, 21 vertical, 2 * 12 diagonal
+        assert_eq!(count, 69);
+
+        let mut count = 0;
+        Gameboard::<4, 4>::new().for_each_window(|_, _| count += 1);
+        assert_eq!(count, 10);
+
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        let mut count = 0;
+        board.for_each_window(|coords, slots| {
+            for (coord, slot) in coords.into_iter().zip(slots) {
+                assert_eq!(board.0[coord.0][coord.1], slot);
+            }
+            if slots.contains(&Some(Player::Player1)) {
+                count += 1;
+            }
+        });
+        // horizontal, vertical, diagonal
+        assert_eq!(count, 3);
+    }
+
+    #[test]
+    fn test_from_cells() {
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        assert_eq!(Gameboard::from_cells(*board.board()), Ok(board));
+        assert_eq!(
+            Gameboard::from_cells([[None; 7]; 6]),
+            Ok(Gameboard::<7, 6>::new())
+        );
+
+        let mut cells = [[None; 4]; 4];
+        cells[1][2] = Some(Player::Player1);
+        assert_eq!(
+            Gameboard::<4, 4>::from_cells(cells),
+            Err(GameboardError::FloatingChip)
+        );
+
+        let mut cells = [[None; 4]; 4];
+        for row in &mut cells {
+            row[0] = Some(Player::Player1);
+            row[3] = Some(Player::Player2);
+        }
+        assert_eq!(
+            Gameboard::<4, 4>::from_cells(cells),
+            Err(GameboardError::MultipleWinners)
+        );
+    }
+
+    #[test]
+    fn test_debug() {
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        board.insert_player_chip(1, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player2).unwrap();
+
+        let debug = std::format!("{board:?}");
+        assert_eq!(
+            debug,
+            r#"Gameboard { rows: ["....", "....", ".O..", ".X.O"], scoring_height: 4 }"#
+        );
+        assert!(!debug.contains("Some(Player1)"));
+
+        let pretty = std::format!("{:#?}", Gameboard::<7, 6>::new());
+        assert_eq!(
+            pretty
+                .lines()
+                .filter(|line| line.contains("......."))
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_status() {
+        let mut board = Gameboard::<4, 4>::new();
+        assert_eq!(board.status(), GameStatus::InProgress);
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        assert_eq!(board.status(), GameStatus::InProgress);
+
+        // won, but not full
+        board.drop_many(0, Player::Player1, 3).unwrap();
+        assert!(!board.gameover());
+        assert_eq!(board.status(), GameStatus::Won(Player::Player1));
+
+        // full without a winner
+        let mut board = Gameboard::<4, 4>::new();
+        for col in [0_usize, 1, 2, 3] {
+            for row in 0..4 {
+                let player = if (row + col / 2).is_multiple_of(2) {
+                    Player::Player1
+                } else {
+                    Player::Player2
+                };
+                board.insert_player_chip(col, player).unwrap();
+            }
+        }
+        assert!(board.gameover());
+        assert_eq!(board.status(), GameStatus::Draw);
+
+        // won with the last move that fills the board
+        board.set_cell(3, 3, None).unwrap();
+        for row in 0..3 {
+            board.set_cell(row, 3, Some(Player::Player2)).unwrap();
+        }
+        board.insert_player_chip(3, Player::Player2).unwrap();
+        assert!(board.gameover());
+        assert_eq!(board.status(), GameStatus::Won(Player::Player2));
+    }
+
+    #[test]
+    fn test_is_reachable() {
+        assert!(Gameboard::<7, 6>::new().is_reachable());
+        let board = midgame_positions().swap_remove(1).into_board();
+        assert!(board.is_reachable());
+
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        assert!(board.is_reachable());
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        assert!(!board.is_reachable());
+        board.insert_player_chip(4, Player::Player2).unwrap();
+        assert!(board.is_reachable());
+        for col in [5, 6] {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        // three more chips of player 1
+        assert!(!board.is_reachable());
+
+        // floating chip
+        let mut board = Gameboard::<7, 6>::new();
+        board.set_cell(1, 0, Some(Player::Player1)).unwrap();
+        assert!(!board.is_reachable());
+    }
+
+    #[test]
+    fn test_from_ascii_lenient() {
+        let mut expected = Gameboard::<7, 6>::new();
+        for (col, player) in [
+            (0, Player::Player1),
+            (0, Player::Player2),
+            (1, Player::Player2),
+            (3, Player::Player1),
+            (3, Player::Player1),
+            (6, Player::Player2),
+        ] {
+            expected.insert_player_chip(col, player).unwrap();
+        }
+
+        // missing top rows, short lines, mixed empty symbols, lowercase
+        // symbols, and trailing whitespace
+        let board = Gameboard::<7, 6>::from_ascii_lenient("o  x   \nXo.x..O\n");
+        assert_eq!(board, Ok(expected));
+        assert_eq!(
+            Gameboard::<7, 6>::from_ascii_lenient(""),
+            Ok(Gameboard::new())
+        );
+
+        assert_eq!(
+            Gameboard::<7, 6>::from_ascii_lenient("XOXOXOXO"),
+            Err(GameboardError::InvalidInput)
+        );
+        assert_eq!(
+            Gameboard::<4, 4>::from_ascii_lenient("\n\n\n\nX"),
+            Err(GameboardError::InvalidInput)
+        );
+        assert_eq!(
+            Gameboard::<7, 6>::from_ascii_lenient("X?O"),
+            Err(GameboardError::InvalidInput)
+        );
+        assert_eq!(
+            Gameboard::<7, 6>::from_ascii_lenient("X\n.\n"),
+            Err(GameboardError::FloatingChip)
+        );
+    }
+
+    #[test]
+    fn test_phase() {
+        let mut board = Gameboard::<4, 4>::new();
+        assert_eq!(board.phase(), Phase::Opening);
+
+        let mut player = Player::Player1;
+        let mut insert = |board: &mut Gameboard<4, 4>, col| {
+            board.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        };
+
+        for col in [0, 1, 2] {
+            insert(&mut board, col);
+        }
+        assert_eq!(board.phase(), Phase::Opening);
+        insert(&mut board, 3);
+        assert_eq!(board.phase(), Phase::Midgame);
+
+        for col in [0, 1, 2, 3] {
+            insert(&mut board, col);
+        }
+        // half-full
+        assert_eq!(board.phase(), Phase::Midgame);
+
+        for col in [1, 0, 3, 2] {
+            insert(&mut board, col);
+        }
+        assert_eq!(board.phase(), Phase::Midgame);
+        insert(&mut board, 0);
+        assert_eq!(board.phase(), Phase::Endgame);
+    }
+
+    #[test]
+    fn test_insert_returning_row() {
+        let mut board = Gameboard::<4, 4>::new();
+        for row in 0..board.height() {
+            assert_eq!(board.insert_returning_row(1, Player::Player1), Ok(row));
+        }
+        assert_eq!(
+            board.insert_returning_row(1, Player::Player1),
+            Err(GameboardError::ColumnFull)
+        );
+        assert_eq!(
+            board.insert_returning_row(4, Player::Player1),
+            Err(GameboardError::InvalidColumn)
+        );
+        assert_eq!(board.insert_returning_row(0, Player::Player2), Ok(0));
+    }
+
+    #[test]
+    fn test_game_snapshot_restore() {
+        let mut game = Game::<7, 6>::new();
+        assert_eq!(game.turn(), Player::Player1);
+        game.insert_player_chip(3, Player::Player1).unwrap();
+        game.insert_player_chip(2, Player::Player2).unwrap();
+        game.insert_player_chip(3, Player::Player1).unwrap();
+
+        let snapshot = game.snapshot();
+        assert_eq!(snapshot.board(), game.board());
+        assert_eq!(snapshot.round(), 3);
+        assert_eq!(
+            snapshot.history(),
+            &[
+                (3, Player::Player1),
+                (2, Player::Player2),
+                (3, Player::Player1)
+            ]
+        );
+        assert_eq!(snapshot.turn(), Player::Player2);
+
+        game.insert_player_chip(4, Player::Player2).unwrap();
+        game.insert_player_chip(4, Player::Player1).unwrap();
+        assert_ne!(game.snapshot(), snapshot);
+        assert_eq!(game.turn(), Player::Player2);
+
+        game.restore(snapshot.clone());
+        assert_eq!(game.snapshot(), snapshot);
+        assert_eq!(game.board(), snapshot.board());
+        assert_eq!(game.round(), 3);
+        assert_eq!(game.history(), snapshot.history());
+        assert_eq!(game.turn(), Player::Player2);
+    }
+
+    #[test]
+    fn test_winning_line_after() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(4, Player::Player1).unwrap();
+
+        assert_eq!(
+            board.winning_line_after(3, Player::Player1),
+            Some([(0, 1), (0, 2), (0, 3), (0, 4)])
+        );
+        assert_eq!(board.winning_line_after(3, Player::Player2), None);
+        assert_eq!(board.winning_line_after(0, Player::Player1), None);
+        assert_eq!(board.winning_line_after(7, Player::Player1), None);
+
+        for _ in 0..3 {
+            board.insert_player_chip(6, Player::Player2).unwrap();
+        }
+        assert_eq!(
+            board.winning_line_after(6, Player::Player2),
+            Some([(0, 6), (1, 6), (2, 6), (3, 6)])
+        );
+    }
+
+    #[test]
+    fn test_stacked_threats() {
+        let mut board = Gameboard::<7, 6>::new();
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        assert_eq!(board.stacked_threats(Player::Player1), 1);
+        assert_eq!(board.stacked_threats(Player::Player2), 0);
+
+        // blocking the lower threat doesn't remove the upper one
+        board.insert_player_chip(3, Player::Player2).unwrap();
+        assert_eq!(board.stacked_threats(Player::Player1), 0);
+        assert!(board.is_winning_move(3, Player::Player1));
+    }
+
+    #[test]
+    fn test_to_nested_vec() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+
+        let rows = board.to_nested_vec();
+        assert_eq!(rows.len(), 6);
+        assert!(rows.iter().all(|row| row.len() == 7));
+        assert_eq!(rows[0][0], Some(Player::Player1));
+        assert_eq!(rows[1][0], Some(Player::Player2));
+        assert_eq!(rows[0][6], Some(Player::Player2));
+        assert_eq!(rows[5][3], None);
+    }
+
+    #[test]
+    fn test_threats_blocked_by() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(1, Player::Player2).unwrap();
+        board.insert_player_chip(2, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player2).unwrap();
+
+        assert_eq!(
+            board.threats_blocked_by(0, Player::Player1).as_slice(),
+            &[(0, 0)]
+        );
+        assert_eq!(
+            board.threats_blocked_by(4, Player::Player1).as_slice(),
+            &[(0, 4)]
+        );
+        assert!(board.threats_blocked_by(5, Player::Player1).is_empty());
+        assert!(board.threats_blocked_by(7, Player::Player1).is_empty());
+    }
+
+    #[test]
+    fn test_append_move_line() {
+        let mut game = Game::<4, 4>::new();
+        let mut log = String::new();
+        for col in [0, 1, 0, 1, 0, 1, 0] {
+            game.append_move_line(&mut log, col).unwrap();
+            game.insert_player_chip(col, game.turn()).unwrap();
+        }
+        assert_eq!(log, "1 -\n2 -\n1 -\n2 -\n1 -\n2 -\n1 1\n");
+
+        // column 0 is full now
+        assert_eq!(
+            game.append_move_line(&mut log, 0),
+            Err(MoveLineError::IllegalMove(GameboardError::ColumnFull))
+        );
+        assert_eq!(
+            game.append_move_line(&mut log, 4),
+            Err(MoveLineError::IllegalMove(GameboardError::InvalidColumn))
+        );
+        assert!(log.ends_with("1 1\n"));
+
+        struct FailingWriter;
+        impl fmt::Write for FailingWriter {
+            fn write_str(&mut self, _: &str) -> fmt::Result {
+                Err(fmt::Error)
+            }
+        }
+        let error = game.append_move_line(&mut FailingWriter, 1).unwrap_err();
+        assert_eq!(error, MoveLineError::Write(fmt::Error));
+        assert!(error.source().is_some());
+    }
+
+    #[test]
+    fn test_threat_balance() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.threat_balance(Player::Player1), 0);
+
+        // open three for player 1 in the bottom row
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(4, Player::Player1).unwrap();
+        // vertical three for player 2
+        board.insert_player_chip(6, Player::Player2).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+
+        assert_eq!(
+            board.immediate_winning_cells(Player::Player1).as_slice(),
+            &[(0, 1), (0, 5)]
+        );
+        assert_eq!(
+            board.immediate_winning_cells(Player::Player2).as_slice(),
+            &[(3, 6)]
+        );
+        assert_eq!(board.threat_balance(Player::Player1), 1);
+        assert_eq!(board.threat_balance(Player::Player2), -1);
+    }
+
+    #[test]
+    fn test_center_control() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.center_control(Player::Player1), 0);
+
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(3, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(2, Player::Player2).unwrap();
+        board.insert_player_chip(4, Player::Player2).unwrap();
+        assert_eq!(board.center_control(Player::Player1), 1);
+        assert_eq!(board.center_control(Player::Player2), -1);
+
+        // both central columns count on even widths
+        let mut board = Gameboard::<8, 6>::new();
+        board.insert_player_chip(3, Player::Player2).unwrap();
+        board.insert_player_chip(4, Player::Player2).unwrap();
+        board.insert_player_chip(4, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        assert_eq!(board.center_control(Player::Player2), 1);
+        assert_eq!(board.center_control(Player::Player1), -1);
+    }
+
+    #[test]
+    fn test_column_string() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(2, Player::Player2).unwrap();
+        board.insert_player_chip(2, Player::Player2).unwrap();
+
+        assert_eq!(board.column_string(2), "XOO...");
+        assert_eq!(board.column_string(0), "......");
+    }
+
+    #[test]
+    fn test_validate_moves() {
+        assert_eq!(validate_moves::<7, 6>(&[]), Ok(()));
+        assert_eq!(validate_moves::<7, 6>(&[3, 3, 2, 4, 0, 6]), Ok(()));
+        assert_eq!(validate_moves::<4, 4>(&[0, 0, 0, 0]), Ok(()));
+
+        assert_eq!(
+            validate_moves::<4, 4>(&[0, 0, 0, 0, 0]),
+            Err(ReplayError {
+                index: 4,
+                error: GameboardError::ColumnFull
+            })
+        );
+        assert_eq!(
+            validate_moves::<7, 6>(&[3, 3, 2, 7, 0]),
+            Err(ReplayError {
+                index: 3,
+                error: GameboardError::InvalidColumn
+            })
+        );
+        // player 1 wins vertically with the 7th move
+        assert_eq!(
+            validate_moves::<7, 6>(&[0, 1, 0, 1, 0, 1, 0, 1]),
+            Err(ReplayError {
+                index: 7,
+                error: GameboardError::GameOver
+            })
+        );
+    }
+
+    #[test]
+    fn test_replay_up_to() {
+        let moves = MIDGAME_MOVES[0];
+        assert_eq!(
+            replay_up_to::<7, 6>(moves, moves.len()),
+            Ok(midgame_positions().swap_remove(0))
+        );
+        assert_eq!(
+            replay_up_to::<7, 6>(moves, moves.len() + 1),
+            replay_up_to::<7, 6>(moves, moves.len())
+        );
+        assert_eq!(replay_up_to::<7, 6>(moves, 0), Ok(Game::new()));
+
+        let game = replay_up_to::<7, 6>(moves, 3).unwrap();
+        assert_eq!(
+            game.history(),
+            [
+                (3, Player::Player1),
+                (3, Player::Player2),
+                (2, Player::Player1)
+            ]
+        );
+
+        assert_eq!(
+            replay_up_to::<4, 4>(&[0, 0, 0, 0, 0], 4).map(|game| game.round()),
+            Ok(4)
+        );
+        assert_eq!(
+            replay_up_to::<4, 4>(&[0, 0, 0, 0, 0], 5),
+            Err(GameboardError::ColumnFull)
+        );
+        assert_eq!(
+            replay_up_to::<7, 6>(&[0, 1, 0, 1, 0, 1, 0, 1], 8),
+            Err(GameboardError::GameOver)
+        );
+    }
+
+    #[test]
+    fn test_collect_stats() {
+        // player 1 builds an open three in the bottom row, player 2 blocks
+        // one of its ends
+        let [p1, p2] = collect_stats::<7, 6>(&[3, 3, 4, 4, 5, 2], Player::Player1);
+        assert_eq!(
+            p1,
+            PlayerStats {
+                moves: 3,
+                center_moves: 1,
+                threats_created: 2,
+                blocks_made: 0,
+            }
+        );
+        assert_eq!(
+            p2,
+            PlayerStats {
+                moves: 3,
+                center_moves: 1,
+                threats_created: 0,
+                blocks_made: 1,
+            }
+        );
+
+        // the same game with swapped roles, and the replay stops at the
+        // illegal move
+        let [p1_swapped, p2_swapped] =
+            collect_stats::<7, 6>(&[3, 3, 4, 4, 5, 2, 9, 0], Player::Player2);
+        assert_eq!(p1_swapped, p2);
+        assert_eq!(p2_swapped, p1);
+
+        assert_eq!(
+            collect_stats::<7, 6>(&[], Player::Player1),
+            [PlayerStats::default(); 2]
+        );
+    }
+
+    #[test]
+    fn test_replay_error_source() {
+        let error = validate_moves::<4, 4>(&[0, 0, 0, 0, 0]).unwrap_err();
+        assert_eq!(error.to_string(), "illegal move at index 4");
+
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.downcast_ref::<GameboardError>(),
+            Some(&GameboardError::ColumnFull)
+        );
+        assert!(source.source().is_none());
+    }
+
+    #[test]
+    fn test_best_threat_cell() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.best_threat_cell(Player::Player1), None);
+
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+        // column 4 opens the bottom row on both sides, column 1 only on one
+        assert_eq!(board.best_threat_cell(Player::Player1), Some((0, 4)));
+        assert_eq!(board.best_threat_cell(Player::Player2), None);
+    }
+
+    #[test]
+    fn test_settle() {
+        let p1 = Some(Player::Player1);
+        let p2 = Some(Player::Player2);
+        let mut board = Gameboard::<4, 4>::new();
+        for (row, col, cell) in [
+            (0, 1, p2),
+            (0, 3, p2),
+            (1, 0, p1),
+            (2, 1, p1),
+            (3, 0, p2),
+            (3, 2, p1),
+        ] {
+            board.set_cell(row, col, cell).unwrap();
+        }
+        assert_eq!(board.validate(), Err(GameboardError::FloatingChip));
+        board.settle();
+        assert_eq!(
+            board,
+            Gameboard::from_cells([
+                [p1, p2, p1, p2],
+                [p2, p1, None, None],
+                [None, None, None, None],
+                [None, None, None, None],
+            ])
+            .unwrap()
+        );
+
+        let mut settled = board.clone();
+        settled.settle();
+        assert_eq!(settled, board);
+    }
+
+    #[test]
+    fn test_cell_symbol() {
+        assert_eq!(cell_symbol(None), ' ');
+        assert_eq!(cell_symbol(Some(Player::Player1)), 'X');
+        assert_eq!(cell_symbol(Some(Player::Player2)), 'O');
+    }
+
+    #[test]
+    fn test_same_position() {
+        let play = |moves: &[usize]| {
+            let mut game = Game::<7, 6>::new();
+            for &col in moves {
+                game.insert_player_chip(col, game.turn()).unwrap();
+            }
+            game
+        };
+
+        let a = play(&[3, 2, 4, 5]);
+        let b = play(&[4, 5, 3, 2]);
+        assert!(a.same_position(&b));
+        assert_ne!(a, b);
+
+        assert!(a.same_position(&a.clone()));
+        assert!(!a.same_position(&play(&[3, 2, 4])));
+        assert!(!a.same_position(&play(&[3, 2, 5, 4])));
+    }
+
+    #[test]
+    fn test_recent_moves() {
+        let mut game = Game::<7, 6>::new();
+        assert!(game.recent_moves(2).is_empty());
+
+        for col in [3, 2, 4, 6, 1] {
+            game.insert_player_chip(col, game.turn()).unwrap();
+        }
+        assert_eq!(game.recent_moves(2), [6, 1]);
+        assert!(game.recent_moves(0).is_empty());
+        assert_eq!(game.recent_moves(10), [3, 2, 4, 6, 1]);
+
+        game.undo().unwrap();
+        assert_eq!(game.recent_moves(2), [4, 6]);
+    }
+
+    #[test]
+    fn test_move_timestamps() {
+        let mut game = Game::<7, 6>::new();
+        game.insert_with_timestamp(3, Player::Player1, 100).unwrap();
+        game.insert_with_timestamp(3, Player::Player2, 250).unwrap();
+        game.insert_with_timestamp(2, Player::Player1, 400).unwrap();
+        assert_eq!(
+            game.insert_with_timestamp(7, Player::Player2, 500),
+            Err(GameboardError::InvalidColumn)
+        );
+        assert_eq!(game.move_timestamps(), [100, 250, 400]);
+        assert_eq!(game.move_timestamps().len(), game.history().len());
+
+        // untimed moves inherit the previous timestamp
+        game.insert_player_chip(4, Player::Player2).unwrap();
+        assert_eq!(game.move_timestamps(), [100, 250, 400, 400]);
+
+        game.undo().unwrap();
+        game.undo().unwrap();
+        assert_eq!(game.move_timestamps(), [100, 250]);
+        assert_eq!(game.move_timestamps().len(), game.history().len());
+
+        let snapshot = game.snapshot();
+        game.edit(|_| {});
+        assert!(game.move_timestamps().is_empty());
+        game.restore(snapshot);
+        assert_eq!(game.move_timestamps(), [100, 250]);
+    }
+
+    #[test]
+    fn test_into_board() {
+        let game = &midgame_positions()[1];
+        let expected = game.board().clone();
+        assert_eq!(game.clone().into_board(), expected);
+        assert_eq!(Gameboard::from(game.clone()), expected);
+
+        let board: Gameboard = Game::new().into();
+        assert_eq!(board, Gameboard::new());
+    }
+
+    #[test]
+    fn test_game_key() {
+        let play = |moves: &[usize]| {
+            let mut game = Game::<7, 6>::new();
+            for &col in moves {
+                game.insert_player_chip(col, game.turn()).unwrap();
+            }
+            game
+        };
+
+        let a = play(&[3, 2, 4, 5]);
+        let b = play(&[4, 5, 3, 2]);
+        assert_eq!(a.board(), b.board());
+        assert_eq!(a.board().to_u128(), b.board().to_u128());
+        assert_ne!(a.game_key(), b.game_key());
+
+        assert_eq!(a.game_key(), play(&[3, 2, 4, 5]).game_key());
+        assert_ne!(a.game_key(), play(&[3, 2, 4]).game_key());
+        assert_ne!(Game::<7, 6>::new().game_key(), play(&[0]).game_key());
+    }
+
+    #[test]
+    fn test_losing_moves() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(1, Player::Player2).unwrap();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player2).unwrap();
+        }
+        assert!(!board.in_check(Player::Player1));
+
+        // filling the slot below the threat of player 2 loses
+        assert_eq!(board.losing_moves(Player::Player1), [3]);
+        assert!(board.losing_moves(Player::Player2).is_empty());
+    }
+
+    #[test]
+    fn test_set_cell() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.set_cell(0, 3, Some(Player::Player1)).unwrap();
+        board.set_cell(1, 3, Some(Player::Player2)).unwrap();
+        assert_eq!(board.board()[0][3], Some(Player::Player1));
+        assert_eq!(board.board()[1][3], Some(Player::Player2));
+        assert_eq!(board.validate(), Ok(()));
+
+        // clearing the bottom slot leaves a floating chip
+        board.set_cell(0, 3, None).unwrap();
+        assert_eq!(board.board()[0][3], None);
+        assert_eq!(board.validate(), Err(GameboardError::FloatingChip));
+        board.set_cell(1, 3, None).unwrap();
+        assert_eq!(board, Gameboard::new());
+
+        assert_eq!(
+            board.set_cell(6, 0, Some(Player::Player1)),
+            Err(GameboardError::InvalidRow)
+        );
+        assert_eq!(
+            board.set_cell(0, 7, Some(Player::Player1)),
+            Err(GameboardError::InvalidColumn)
+        );
+        assert_eq!(board, Gameboard::new());
+    }
+
+    #[test]
+    fn test_player_at_move() {
+        for first in [Player::Player1, Player::Player2] {
+            let mut game = Game::<7, 6>::new();
+            assert_eq!(game.player_at_move(0), None);
+
+            let mut player = first;
+            for col in [3, 3, 2, 4, 0] {
+                game.insert_player_chip(col, player).unwrap();
+                player = player.opponent();
+            }
+
+            for index in [0, 2, 4] {
+                assert_eq!(game.player_at_move(index), Some(first));
+            }
+            for index in [1, 3] {
+                assert_eq!(game.player_at_move(index), Some(first.opponent()));
+            }
+            assert_eq!(game.player_at_move(5), None);
+        }
+    }
+
+    #[test]
+    fn test_has_any_winner() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert!(!board.has_any_winner());
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+            board.insert_player_chip(col, Player::Player2).unwrap();
+        }
+        assert!(!board.has_any_winner());
+
+        let mut won_by_player1 = board.clone();
+        won_by_player1
+            .insert_player_chip(3, Player::Player1)
+            .unwrap();
+        assert!(won_by_player1.has_any_winner());
+
+        let mut won_by_player2 = Gameboard::<7, 6>::new();
+        for _ in 0..4 {
+            won_by_player2
+                .insert_player_chip(6, Player::Player2)
+                .unwrap();
+        }
+        assert!(won_by_player2.has_any_winner());
+    }
+
+    #[test]
+    fn test_board_too_small() {
+        assert_eq!(
+            Gameboard::<3, 6>::try_new(),
+            Err(GameboardError::BoardTooSmall {
+                width: 3,
+                height: 6,
+                required: 4,
+            })
+        );
+        assert_eq!(
+            Gameboard::<7, 2>::from_cells([[None; 7]; 2]),
+            Err(GameboardError::BoardTooSmall {
+                width: 7,
+                height: 2,
+                required: 4,
+            })
+        );
+        assert_eq!(Gameboard::<4, 4>::try_new(), Ok(Gameboard::new()));
+        assert!(matches!(
+            Game::<4, 3>::try_new(),
+            Err(GameboardError::BoardTooSmall { height: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn test_swapped() {
+        let mut board = Gameboard::<7, 6>::new();
+        for (col, player) in [
+            (3, Player::Player1),
+            (3, Player::Player2),
+            (2, Player::Player1),
+        ] {
+            board.insert_player_chip(col, player).unwrap();
+        }
+        let count = |board: &Gameboard<7, 6>, player| {
+            board
+                .board()
+                .iter()
+                .flatten()
+                .filter(|&&slot| slot == Some(player))
+                .count()
+        };
+
+        let swapped = board.swapped();
+        assert_eq!(count(&swapped, Player::Player1), 1);
+        assert_eq!(count(&swapped, Player::Player2), 2);
+        assert_eq!(swapped.board()[0][3], Some(Player::Player2));
+        assert_eq!(swapped.board()[1][3], Some(Player::Player1));
+        assert_eq!(swapped.swapped(), board);
+        assert_eq!(Gameboard::<7, 6>::new().swapped(), Gameboard::new());
+    }
+
+    #[test]
+    fn test_to_string_highlighting() {
+        let mut board = Gameboard::<4, 4>::new();
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        let line = board.winning_line_after(3, Player::Player1).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+
+        assert_eq!(
+            board.to_string_highlighting(&[]),
+            "            \n            \n O          \n X  X  X  X \n"
+        );
+        assert_eq!(
+            board.to_string_highlighting(&line),
+            "            \n            \n O          \n[X][X][X][X]\n"
+        );
+    }
+
+    #[test]
+    fn test_to_emoji_string() {
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player2).unwrap();
+
+        assert_eq!(
+            board.to_emoji_string(),
+            "⚪⚪⚪⚪\n⚪⚪⚪⚪\n🟡⚪⚪⚪\n🔴⚪⚪🟡\n"
+        );
+        assert_eq!(
+            board.to_string_with_glyphs(["..", "X ", "O "]),
+            "........\n........\nO ......\nX ....O \n"
+        );
+    }
+
+    #[test]
+    fn test_available_columns_iter_matches_scan() {
+        let mut rng = Rng::new(42);
+        let mut board = Gameboard::<7, 6>::new();
+        let mut moves = Vec::new();
+        for _ in 0..2000 {
+            let col = (rng.next_u64() % 7) as usize;
+            // undo the last move every third step on average
+            if rng.next_u64().is_multiple_of(3) {
+                if let Some(col) = moves.pop() {
+                    let row = 6 - board.free_slots_in_column(col) - 1;
+                    board.set_cell(row, col, None).unwrap();
+                }
+            } else if board.insert_player_chip(col, Player::Player1).is_ok() {
+                moves.push(col);
+            }
+
+            let scanned = (0..7)
+                .filter(|&col| board.next_slot_in_column(col).is_some())
+                .collect::<Vec<_>>();
+            assert_eq!(board.available_columns_iter().collect::<Vec<_>>(), scanned);
+            assert_eq!(board.legal_move_count(), scanned.len());
+        }
+    }
+
+    #[test]
+    fn test_min_max_height() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.column_heights(), [0; 7]);
+        assert_eq!(board.min_height(), 0);
+        assert_eq!(board.max_height(), 0);
+
+        for col in 0..7 {
+            board.drop_many(col, Player::Player1, col % 3 + 1).unwrap();
+        }
+        board.insert_player_chip(2, Player::Player2).unwrap();
+        assert_eq!(board.column_heights(), [1, 2, 4, 1, 2, 3, 1]);
+        assert_eq!(board.min_height(), 1);
+        assert_eq!(board.max_height(), 4);
+    }
+
+    #[test]
+    fn test_nearest_legal() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.nearest_legal(3), Some(3));
+        for col in [2, 3, 4] {
+            board.drop_many(col, Player::Player1, 6).unwrap();
+        }
+
+        // to the left
+        assert_eq!(board.nearest_legal(2), Some(1));
+        // to the right
+        assert_eq!(board.nearest_legal(4), Some(5));
+        // equidistant
+        assert_eq!(board.nearest_legal(3), Some(1));
+        // columns beyond the board
+        assert_eq!(board.nearest_legal(10), Some(6));
+
+        for col in [0, 1, 5, 6] {
+            board.drop_many(col, Player::Player2, 6).unwrap();
+        }
+        assert_eq!(board.nearest_legal(3), None);
+    }
+
+    #[test]
+    fn test_drops_until_row() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.drops_until_row(0, 0), Some(1));
+        assert_eq!(board.drops_until_row(0, 5), Some(6));
+
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(3, Player::Player2).unwrap();
+        assert_eq!(board.drops_until_row(3, 2), Some(1));
+        assert_eq!(board.drops_until_row(3, 4), Some(3));
+
+        // taken slots and slots outside of the board can't be reached
+        assert_eq!(board.drops_until_row(3, 1), None);
+        assert_eq!(board.drops_until_row(3, 6), None);
+        assert_eq!(board.drops_until_row(7, 0), None);
+        for _ in 0..4 {
+            board.insert_player_chip(3, Player::Player1).unwrap();
+        }
+        assert_eq!(board.drops_until_row(3, 5), None);
+    }
+
+    #[test]
+    fn test_drop_many() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(2, Player::Player2).unwrap();
+        assert_eq!(board.drop_many(2, Player::Player1, 10), Ok(5));
+        assert_eq!(board.free_slots_in_column(2), 0);
+        assert!(!board.available_columns_iter().any(|col| col == 2));
+        assert_eq!(board.drop_many(2, Player::Player1, 1), Ok(0));
+
+        assert_eq!(board.drop_many(0, Player::Player1, 3), Ok(3));
+        assert_eq!(board.free_slots_in_column(0), 3);
+        assert_eq!(board.drop_many(0, Player::Player2, 0), Ok(0));
+        assert_eq!(
+            board.drop_many(7, Player::Player1, 1),
+            Err(GameboardError::InvalidColumn)
+        );
+    }
+
+    #[test]
+    fn test_board_view() {
+        let mut small = Gameboard::<4, 4>::new();
+        small.insert_player_chip(1, Player::Player1).unwrap();
+        let mut large = Gameboard::<9, 7>::new();
+        large.insert_player_chip(8, Player::Player2).unwrap();
+
+        let boards: [Box<dyn BoardView>; 2] = [Box::new(small), Box::new(large)];
+        let sizes = boards
+            .iter()
+            .map(|board| (board.width(), board.height()))
+            .collect::<Vec<_>>();
+        assert_eq!(sizes, [(4, 4), (9, 7)]);
+        assert_eq!(boards[0].cell(0, 1), Some(Player::Player1));
+        assert_eq!(boards[0].cell(1, 1), None);
+        assert_eq!(boards[1].cell(0, 8), Some(Player::Player2));
+    }
+
+    #[test]
+    fn test_game_undo() {
+        let mut game = Game::<7, 6>::new();
+        assert_eq!(game.undo(), Err(GameboardError::NothingToUndo));
+
+        for col in [3, 3, 4] {
+            game.insert_player_chip(col, game.turn()).unwrap();
+        }
+        let after_two_moves = {
+            let mut game = Game::<7, 6>::new();
+            game.insert_player_chip(3, Player::Player1).unwrap();
+            game.insert_player_chip(3, Player::Player2).unwrap();
+            game
+        };
+
+        assert_eq!(game.undo(), Ok((4, Player::Player1)));
+        assert_eq!(game, after_two_moves);
+        assert_eq!(game.undo(), Ok((3, Player::Player2)));
+        assert_eq!(game.undo(), Ok((3, Player::Player1)));
+        assert_eq!(game, Game::new());
+        assert_eq!(game.undo(), Err(GameboardError::NothingToUndo));
+    }
+
+    #[test]
+    fn test_can_win_within() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+
+        // column 4 creates two threats in the bottom row
+        for plies in 0..3 {
+            assert!(!board.can_win_within(Player::Player1, plies));
+        }
+        assert!(board.can_win_within(Player::Player1, 3));
+        assert!(board.can_win_within(Player::Player1, 4));
+        assert!(!board.can_win_within(Player::Player2, 3));
+
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        assert!(board.can_win_within(Player::Player1, 1));
+    }
+
+    #[test]
+    fn test_scoring_height() {
+        assert_eq!(
+            Gameboard::<7, 6>::with_scoring_height(7),
+            Err(GameboardError::InvalidRow)
+        );
+        assert_eq!(Gameboard::<7, 6>::new().scoring_height(), 6);
+
+        let mut board = Gameboard::<7, 6>::with_scoring_height(5).unwrap();
+        assert_eq!(board.scoring_height(), 5);
+        assert_eq!(board.height(), 6);
+
+        // vertical series that reaches into the buffer row
+        for _ in 0..2 {
+            board.insert_player_chip(0, Player::Player2).unwrap();
+        }
+        for _ in 0..4 {
+            board.insert_player_chip(0, Player::Player1).unwrap();
+        }
+        assert!(!board.check_for_winner(Player::Player1));
+        assert_eq!(board.status(), GameStatus::InProgress);
+        assert_eq!(board.winning_direction(Player::Player1), None);
+
+        // resizing keeps the scoring height, the compact encoding drops it
+        assert_eq!(board.resize::<7, 8>().unwrap().scoring_height(), 5);
+        assert_eq!(board.resize::<7, 4>(), Err(GameboardError::ColumnFull));
+        assert_eq!(
+            Gameboard::<7, 6>::new()
+                .resize::<7, 8>()
+                .unwrap()
+                .scoring_height(),
+            8
+        );
+        let unpacked =
+            Gameboard::<5, 6>::from_u128(board.resize::<5, 6>().unwrap().to_u128().unwrap());
+        assert_eq!(unpacked.scoring_height(), 6);
+        assert!(unpacked.check_for_winner(Player::Player1));
+
+        // the same series one row lower counts
+        let mut board = Gameboard::<7, 6>::with_scoring_height(5).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        for _ in 0..4 {
+            board.insert_player_chip(0, Player::Player1).unwrap();
+        }
+        assert!(board.check_for_winner(Player::Player1));
+        assert_eq!(
+            board.winning_direction(Player::Player1),
+            Some(Direction::Vertical)
+        );
+    }
+
+    #[test]
+    fn test_content_key() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert!(board.content_key().is_empty());
+
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(1, Player::Player2).unwrap();
+        assert_eq!(board.content_key(), [(0, 0, 2), (0, 1, 1), (1, 1, 2)]);
+
+        let mut same = Gameboard::<7, 6>::new();
+        same.insert_player_chip(0, Player::Player2).unwrap();
+        same.insert_player_chip(1, Player::Player1).unwrap();
+        same.insert_player_chip(1, Player::Player2).unwrap();
+        assert_eq!(same.content_key(), board.content_key());
+
+        assert_ne!(board.mirrored().content_key(), board.content_key());
+    }
+
+    #[test]
+    fn test_fork_cells() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert!(board.fork_cells(Player::Player1).is_empty());
+
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+        // column 1 creates only one threat, as player 2 blocks column 0
+        assert_eq!(board.fork_cells(Player::Player1), [(0, 4)]);
+        assert!(board.fork_cells(Player::Player2).is_empty());
+    }
+
+    #[test]
+    fn test_double_threat_count() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.double_threat_count(Player::Player1), 0);
+
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        board.insert_player_chip(6, Player::Player2).unwrap();
+        assert_eq!(board.double_threat_count(Player::Player1), 1);
+        assert_eq!(board.double_threat_count(Player::Player2), 0);
+
+        // without player 2 at the edge, columns 1 and 4 both create a fork
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        assert_eq!(
+            board.double_threat_count(Player::Player1),
+            board.fork_cells(Player::Player1).len()
+        );
+        assert_eq!(board.double_threat_count(Player::Player1), 2);
+    }
+
+    #[test]
+    fn test_game_edit() {
+        let mut game = Game::<7, 6>::new();
+        game.insert_player_chip(6, Player::Player1).unwrap();
+
+        game.edit(|board| {
+            for col in 0..3 {
+                board.insert_player_chip(col, Player::Player1).unwrap();
+                board.insert_player_chip(col, Player::Player2).unwrap();
+            }
+        });
+        assert_eq!(game.round(), 7);
+        assert!(game.history().is_empty());
+        assert_eq!(game.turn(), Player::Player2);
+        assert_eq!(game.board().status(), GameStatus::InProgress);
+
+        game.edit(|board| {
+            board.insert_player_chip(3, Player::Player2).unwrap();
+            board.insert_player_chip(3, Player::Player2).unwrap();
+        });
+        assert_eq!(game.round(), 9);
+        assert_eq!(game.turn(), Player::Player1);
+        assert_eq!(game.board().status(), GameStatus::Won(Player::Player2));
+    }
+
+    #[test]
+    fn test_run_length_histogram() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.run_length_histogram(Player::Player1), [0; 5]);
+
+        // a run of 3 in the bottom row, a vertical run of 2, and a single chip
+        for col in 0..3 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        board.drop_many(4, Player::Player1, 2).unwrap();
+        board.insert_player_chip(6, Player::Player1).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        assert_eq!(board.run_length_histogram(Player::Player1), [0, 1, 1, 1, 0]);
+        assert_eq!(board.run_length_histogram(Player::Player2), [0, 1, 0, 0, 0]);
+
+        // runs longer than a series count as a series, and each direction
+        // counts separately
+        let mut board = Gameboard::<7, 6>::new();
+        for col in 0..5 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        board.insert_player_chip(1, Player::Player1).unwrap();
+        assert_eq!(board.run_length_histogram(Player::Player1), [0, 0, 3, 0, 1]);
+
+        // the histogram ends at the series length of the board
+        let mut board = Gameboard::<5, 5, 3>::new();
+        for col in 0..4 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        assert_eq!(board.run_length_histogram(Player::Player1), [0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn test_line_at() {
+        let p1 = Some(Player::Player1);
+        let p2 = Some(Player::Player2);
+        let mut board = Gameboard::<7, 6>::new();
+        for (col, player) in [(0, p1), (1, p2), (1, p1), (2, p2), (2, p2), (2, p1)] {
+            board.insert_player_chip(col, player.unwrap()).unwrap();
+        }
+
+        assert_eq!(
+            board.line_at(0, 0, Direction::Horizontal),
+            Some([p1, p2, p2, None])
+        );
+        assert_eq!(
+            board.line_at(0, 2, Direction::Vertical),
+            Some([p2, p2, p1, None])
+        );
+        assert_eq!(
+            board.line_at(0, 0, Direction::DiagUp),
+            Some([p1, p1, p1, None])
+        );
+        assert_eq!(
+            board.line_at(3, 0, Direction::DiagDown),
+            Some([None, None, p2, None])
+        );
+
+        assert_eq!(board.line_at(0, 4, Direction::Horizontal), None);
+        assert_eq!(board.line_at(3, 0, Direction::Vertical), None);
+        assert_eq!(board.line_at(3, 0, Direction::DiagUp), None);
+        assert_eq!(board.line_at(2, 0, Direction::DiagDown), None);
+        assert_eq!(board.line_at(6, 0, Direction::Horizontal), None);
+        assert_eq!(board.line_at(0, 7, Direction::Vertical), None);
+    }
+
+    #[test]
+    fn test_game_summary() {
+        let play = |moves: &[usize]| {
+            let mut game = Game::<4, 4>::new();
+            for &col in moves {
+                game.insert_player_chip(col, game.turn()).unwrap();
+            }
+            game
+        };
+
+        assert_eq!(play(&[]).summary(), "In progress after 0 moves");
+        assert_eq!(
+            play(&[0, 1, 0, 1, 0, 1, 0]).summary(),
+            "Player 1 won in 7 moves"
+        );
+        assert_eq!(
+            play(&[3, 0, 1, 0, 1, 0, 2, 0]).summary(),
+            "Player 2 won in 8 moves"
+        );
+
+        // rows alternate between `XXOO` and `OOXX`
+        let draw = play(&[0, 2, 1, 3, 2, 0, 3, 1, 0, 2, 1, 3, 2, 0, 3, 1]);
+        assert_eq!(draw.board().status(), GameStatus::Draw);
+        assert_eq!(draw.summary(), "Draw after 16 moves");
+    }
+
+    #[test]
+    fn test_is_zugzwang() {
+        let p1 = Some(Player::Player1);
+        let p2 = Some(Player::Player2);
+        // the only legal move enables the threat of player 2 in row 1
+        let board = Gameboard::<4, 4>::from_cells([
+            [p1, p1, p2, None],
+            [p2, p2, p2, None],
+            [p1, p1, p2, None],
+            [p2, p1, p1, None],
+        ])
+        .unwrap();
+        assert!(board.is_zugzwang(Player::Player1));
+        assert!(!board.is_zugzwang(Player::Player2));
+
+        assert!(!Gameboard::<7, 6>::new().is_zugzwang(Player::Player1));
+    }
+
+    #[test]
+    fn test_try_apply_sequence() {
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        for _ in 0..4 {
+            board.insert_player_chip(3, Player::Player2).unwrap();
+        }
+        let before = board.clone();
+
+        assert_eq!(
+            board.try_apply_sequence(&[
+                (0, Player::Player2),
+                (1, Player::Player1),
+                (3, Player::Player2),
+                (2, Player::Player1),
+            ]),
+            Err(GameboardError::ColumnFull)
+        );
+        assert_eq!(board, before);
+
+        assert_eq!(
+            board.try_apply_sequence(&[(0, Player::Player2), (4, Player::Player1)]),
+            Err(GameboardError::InvalidColumn)
+        );
+        assert_eq!(board, before);
+
+        board
+            .try_apply_sequence(&[(0, Player::Player2), (1, Player::Player1)])
+            .unwrap();
+        assert_eq!(board.board()[1][0], Some(Player::Player2));
+        assert_eq!(board.board()[0][1], Some(Player::Player1));
+    }
+
+    #[test]
+    fn test_player_u8() {
+        assert_eq!(Player::Player1 as u8, 1);
+        assert_eq!(Player::Player2 as u8, 2);
+        for player in [Player::Player1, Player::Player2] {
+            assert_eq!(Player::try_from_u8(player.as_u8()), Some(player));
+        }
+        assert_eq!(Player::try_from_u8(0), None);
+        assert_eq!(Player::try_from_u8(3), None);
+        assert_eq!(core::mem::size_of::<Player>(), 1);
+    }
+
+    #[test]
+    fn test_immediate_win_count() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.immediate_win_count(Player::Player1), 0);
+
+        for col in 1..4 {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        assert_eq!(board.immediate_win_count(Player::Player1), 2);
+        assert_eq!(
+            board.immediate_win_count(Player::Player1),
+            board.immediate_winning_cells(Player::Player1).len()
+        );
+        assert_eq!(board.immediate_win_count(Player::Player2), 0);
+    }
+
+    #[test]
+    fn test_undo_move() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.undo_move(3), Err(GameboardError::NothingToUndo));
+        assert_eq!(board.undo_move(7), Err(GameboardError::InvalidColumn));
+
+        for _ in 0..3 {
+            board.insert_player_chip(3, Player::Player1).unwrap();
+            board.insert_player_chip(3, Player::Player2).unwrap();
+        }
+        assert_eq!(board.legal_move_count(), 6);
+        for _ in 0..3 {
+            assert_eq!(board.undo_move(3), Ok(Player::Player2));
+            assert_eq!(board.undo_move(3), Ok(Player::Player1));
+        }
+        assert_eq!(board, Gameboard::new());
+        assert_eq!(board.legal_move_count(), 7);
+        assert_eq!(board.undo_move(3), Err(GameboardError::NothingToUndo));
+    }
+
+    #[test]
+    fn test_columns_with_threats() {
+        let mut board = Gameboard::<7, 6>::new();
+        assert_eq!(board.columns_with_threats(Player::Player1), [false; 7]);
+
+        for col in 1..4 {
+            board.insert_player_chip(col, Player::Player2).unwrap();
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        board.insert_player_chip(4, Player::Player1).unwrap();
+        // The threat of player 1 in column 0 isn't playable yet.
+        assert_eq!(
+            board.columns_with_threats(Player::Player1),
+            [false, false, false, false, true, false, false]
+        );
+        assert_eq!(
+            board.columns_with_threats(Player::Player2),
+            [true, false, false, false, false, false, false]
+        );
+    }
+
+    #[test]
+    fn test_resize() {
+        let mut tall = Gameboard::<4, 8>::new();
+        for player in [Player::Player1, Player::Player2, Player::Player1] {
+            tall.insert_player_chip(0, player).unwrap();
+        }
+        tall.insert_player_chip(3, Player::Player2).unwrap();
+        tall.insert_player_chip(3, Player::Player2).unwrap();
+
+        let wide = tall.resize::<8, 4>().unwrap();
+        assert_eq!(
+            wide,
+            Gameboard::<8, 4>::from_ascii_lenient("X\nO..O\nX..O").unwrap()
+        );
+        assert_eq!(wide.resize::<4, 8>(), Ok(tall.clone()));
+        // columns without chips may be dropped
+        assert_eq!(
+            wide.resize::<4, 4>(),
+            Gameboard::<4, 4>::from_ascii_lenient("X\nO..O\nX..O")
+        );
+        assert_eq!(
+            wide.resize::<4, 3>().unwrap_err(),
+            GameboardError::BoardTooSmall {
+                width: 4,
+                height: 3,
+                required: 4
+            }
+        );
+
+        tall.insert_player_chip(1, Player::Player1).unwrap();
+        assert_eq!(
+            tall.resize::<5, 5>().unwrap().column_heights(),
+            [3, 1, 0, 2, 0]
+        );
+        tall.insert_player_chip(0, Player::Player2).unwrap();
+        tall.insert_player_chip(0, Player::Player2).unwrap();
+        assert_eq!(tall.resize::<8, 4>(), Err(GameboardError::ColumnFull));
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let board = Gameboard::<7, 6>::new();
+        assert_eq!(board.evaluate(Player::Player1), 0);
+
+        let mut two = board.clone();
+        let mut three = board;
+        for col in 0..2 {
+            two.insert_player_chip(col, Player::Player1).unwrap();
+            three.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        three.insert_player_chip(2, Player::Player1).unwrap();
+        assert!(three.evaluate(Player::Player1) > two.evaluate(Player::Player1));
+        assert!(two.evaluate(Player::Player1) > 0);
+        assert_eq!(
+            two.evaluate(Player::Player2),
+            -two.evaluate(Player::Player1)
+        );
+        assert!(three.evaluate(Player::Player2) < 0);
+
+        // windows have the series length of the board
+        let mut one = Gameboard::<5, 5, 3>::new();
+        one.insert_player_chip(0, Player::Player1).unwrap();
+        let mut two = one.clone();
+        two.insert_player_chip(1, Player::Player1).unwrap();
+        assert!(two.evaluate(Player::Player1) > one.evaluate(Player::Player1));
+        assert!(one.evaluate(Player::Player1) > 0);
+    }
 }
@@ -22,8 +22,25 @@
 extern crate alloc;
 
 mod ai_player;
+mod driver;
+mod eval;
+mod fixtures;
 mod game;
 mod minmax;
+mod rng;
+mod threats;
+mod transposition;
 
-pub use ai_player::search_best_move;
+pub use ai_player::{search_best_move, search_best_move_restricted, try_search_best_move};
+pub use driver::play_out;
+pub use eval::{EvalWeights, evaluate_board, evaluate_forks, window_counts};
+pub use fixtures::{MIDGAME_MOVES, midgame_positions};
 pub use game::*;
+pub use minmax::{
+    EVALUATED_MAX_DEPTH, MAX_DEPTH, SearchConfig, distance_to_win, minmax_search_depth,
+    minmax_search_with_table, move_outcome_map, root_move_report, search_best_move_aspiration,
+    search_best_move_node_limited, search_with_config, search_with_objective,
+};
+pub use rng::Rng;
+pub use threats::find_forced_win;
+pub use transposition::{DEFAULT_TABLE_CAPACITY, TranspositionTable};
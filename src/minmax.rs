@@ -1,72 +1,329 @@
 //! MinMax algorithmic search for Vier gewinnt.
 
-use crate::{Gameboard, Player};
+use crate::{Game, Gameboard, Player, TranspositionTable};
 use alloc::vec::Vec;
 use rayon::iter::IntoParallelIterator;
 use rayon::iter::ParallelIterator;
 
+/// Predicate for positions that count as a win for the target player in
+/// addition to a connected series.
+type GoalFn<'a, const W: usize, const H: usize> = &'a (dyn Fn(&Gameboard<W, H>) -> bool + Sync);
+
+/// Options of a MinMax search, see [`search_with_config`].
+///
+/// The default is the configuration of the AI, e.g., of
+/// [`crate::search_best_move`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SearchConfig {
+    /// Nodes deeper than this are not expanded any further. Deeper searches
+    /// play stronger but take exponentially more time.
+    pub max_depth: usize,
+    /// The moves at the root are always searched in parallel. Below the
+    /// root, the moves of a node are only searched in parallel if at least
+    /// this many plies remain. `usize::MAX` only splits the root.
+    pub parallel_min_remaining_depth: usize,
+    /// Score of a draw from the perspective of the searching player instead
+    /// of `0`. A slightly negative score, e.g., `-1`, makes the AI keep the
+    /// game open instead of simplifying into a draw when it can't force a
+    /// win. If it isn't `0`, positions where nobody can win anymore count as
+    /// draws right away. The score should stay well above the score of a
+    /// loss, i.e., above `-max_depth`.
+    pub draw_score: i32,
+    /// Whether positions at the depth limit are scored with
+    /// [`Gameboard::evaluate`] instead of treating them as undecided. Forced
+    /// wins and losses still outrank every heuristic score, but among
+    /// undecided moves, the AI picks the one with the better position.
+    ///
+    /// On by default. Evaluating the leaves makes a search of the same depth
+    /// about three to four times slower, see the `evaluated_leaves`
+    /// benchmark, so the default depth is [`EVALUATED_MAX_DEPTH`] instead of
+    /// [`MAX_DEPTH`].
+    pub evaluate_leaves: bool,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: EVALUATED_MAX_DEPTH,
+            parallel_min_remaining_depth: PARALLEL_MIN_REMAINING_DEPTH,
+            draw_score: 0,
+            evaluate_leaves: true,
+        }
+    }
+}
+
+/// Configuration that is shared by all nodes of a single search.
+#[derive(Clone, Copy)]
+struct SearchContext<'a, const W: usize, const H: usize> {
+    target_player: Player,
+    is_goal: GoalFn<'a, W, H>,
+    /// If set, only these columns are considered at the root.
+    root_moves: Option<&'a [usize]>,
+    /// Nodes deeper than this are not expanded any further.
+    max_depth: usize,
+    /// Whether the moves at the root are searched in parallel.
+    parallel: bool,
+    /// Below the root, the moves of a node are only searched in parallel if
+    /// at least this many plies remain, i.e., if the subtrees are large
+    /// enough to be worth a task each.
+    parallel_min_remaining_depth: usize,
+    /// Score of a draw from the perspective of the target player. A negative
+    /// score makes the target player prefer unresolved lines over draws. If
+    /// it isn't `0`, positions where nobody can win anymore count as draws
+    /// right away.
+    draw_score: i32,
+    /// Whether positions at the depth limit are scored with
+    /// [`Gameboard::evaluate`] instead of `0`.
+    evaluate_leaves: bool,
+    /// The search is aborted once it visited more nodes. Only reliable for
+    /// sequential searches, as parallel tasks count their nodes separately.
+    max_nodes: u64,
+}
+
+impl<const W: usize, const H: usize> SearchContext<'_, W, H> {
+    /// Context for a regular search without restrictions whose scores only
+    /// tell forced wins and losses apart from undecided positions, e.g., to
+    /// derive the distance to a win.
+    fn new(target_player: Player) -> Self {
+        Self {
+            target_player,
+            is_goal: &|_| false,
+            root_moves: None,
+            max_depth: MAX_DEPTH,
+            parallel: true,
+            parallel_min_remaining_depth: PARALLEL_MIN_REMAINING_DEPTH,
+            draw_score: 0,
+            evaluate_leaves: false,
+            max_nodes: u64::MAX,
+        }
+    }
+
+    /// Context for a regular search with the options of `config`, e.g., with
+    /// the [`SearchConfig::default`] of the AI.
+    fn with_config(target_player: Player, config: &SearchConfig) -> Self {
+        Self {
+            max_depth: config.max_depth,
+            parallel_min_remaining_depth: config.parallel_min_remaining_depth,
+            draw_score: config.draw_score,
+            evaluate_leaves: config.evaluate_leaves,
+            ..Self::new(target_player)
+        }
+    }
+
+    /// Returns whether the moves of a node at `depth` are searched in
+    /// parallel.
+    const fn is_parallel_at(&self, depth: usize) -> bool {
+        self.parallel && (depth == 0 || self.max_depth - depth >= self.parallel_min_remaining_depth)
+    }
+
+    /// Returns whether `gameboard` at `depth` counts as a draw.
+    fn is_draw(&self, gameboard: &Gameboard<W, H>, depth: usize) -> bool {
+        // Dead draws would end with a score of 0 anyway, so only look for
+        // them if draws score differently. The root still has to pick one of
+        // its legal moves.
+        gameboard.gameover() || (depth > 0 && self.draw_score != 0 && gameboard.is_dead_draw())
+    }
+
+    /// Factor for scores of decided games; must be more than the depth of
+    /// the deepest nodes, `max_depth + 1`, so that every win has a positive
+    /// score. With evaluated leaves, it is also more than [`MAX_EVAL`], so
+    /// that every win outranks every heuristic score.
+    const fn score_factor(&self) -> i32 {
+        let factor = self.max_depth as i32 + 2;
+        if self.evaluate_leaves {
+            factor + MAX_EVAL
+        } else {
+            factor
+        }
+    }
+
+    /// Returns the score of `gameboard` when the search stops at the depth
+    /// limit.
+    fn leaf_score(&self, gameboard: &Gameboard<W, H>) -> i32 {
+        if self.evaluate_leaves {
+            gameboard
+                .evaluate(self.target_player)
+                .clamp(-MAX_EVAL, MAX_EVAL)
+        } else {
+            0
+        }
+    }
+}
+
+/// Mutable state of a search. Parallel tasks use their own state, which is
+/// merged afterwards.
+#[derive(Debug)]
+struct SearchState<'a, const W: usize, const H: usize> {
+    table: TranspositionTable<W, H>,
+    /// Tables of the enclosing searches of a parallel task, innermost last.
+    /// They are only read, as other tasks read them at the same time.
+    shared: Vec<&'a TranspositionTable<W, H>>,
+    /// Number of visited nodes.
+    nodes: u64,
+    /// Number of parallel tasks that were spawned.
+    tasks: u64,
+}
+
+impl<'a, const W: usize, const H: usize> SearchState<'a, W, H> {
+    const fn new(table: TranspositionTable<W, H>) -> Self {
+        Self {
+            table,
+            shared: Vec::new(),
+            nodes: 0,
+            tasks: 0,
+        }
+    }
+
+    /// Creates the state of a parallel task: it starts with an empty table
+    /// of the same configuration but can read the tables of `self`.
+    fn task_state(&self) -> SearchState<'_, W, H> {
+        let mut shared = self.shared.clone();
+        shared.push(&self.table);
+        SearchState {
+            shared,
+            tasks: 1,
+            ..SearchState::new(self.table.empty_like())
+        }
+    }
+
+    /// Releases the shared tables, so that the state can be merged into
+    /// the state it was created from.
+    fn into_owned(self) -> SearchState<'static, W, H> {
+        SearchState {
+            table: self.table,
+            shared: Vec::new(),
+            nodes: self.nodes,
+            tasks: self.tasks,
+        }
+    }
+
+    /// Looks up the position in the own table and then in the shared ones,
+    /// see [`TranspositionTable::get`].
+    fn lookup(
+        &mut self,
+        gameboard: &Gameboard<W, H>,
+        side_to_move: Player,
+        remaining_depth: usize,
+    ) -> Option<(Option<usize>, i32)> {
+        if let Some(result) = self.table.get(gameboard, side_to_move, remaining_depth) {
+            return Some(result);
+        }
+        let result = self
+            .shared
+            .iter()
+            .rev()
+            .find_map(|table| table.peek(gameboard, side_to_move, remaining_depth))?;
+        self.table.record_hit();
+        Some(result)
+    }
+
+    fn merge(&mut self, other: SearchState<'_, W, H>) {
+        self.table.merge(other.table);
+        self.nodes += other.nodes;
+        self.tasks += other.tasks;
+    }
+}
+
+/// Whether the current player maximizes or minimizes the score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Objective {
+    /// The target player is to move.
+    Max,
+    /// The opponent of the target player is to move.
+    Min,
+}
+
+impl Objective {
+    /// Score that every real score is at least as good as.
+    const fn initial_score(self) -> i32 {
+        match self {
+            Self::Max => i32::MIN,
+            Self::Min => i32::MAX,
+        }
+    }
+
+    /// Returns whether `new` is strictly better than `best`.
+    const fn is_better(self, new: i32, best: i32) -> bool {
+        match self {
+            Self::Max => new > best,
+            Self::Min => new < best,
+        }
+    }
+}
+
 /// Searches for the best possible move for the current player at the given
 /// search depth using the minimax algorithm, with optional parallelization at
-/// the top search level.
+/// the top search level and at levels with large remaining subtrees.
 ///
 /// This function evaluates all legal moves from the current game state by
 /// simulating each move, updating the game board, and performing a recursive
 /// minimax search via [`minmax_search_recursive`].
 fn search_best_move_in_depth<const W: usize, const H: usize>(
-    gameboard: &Gameboard<W, H>,
-    target_player: Player,
+    gameboard: &mut Gameboard<W, H>,
+    ctx: SearchContext<W, H>,
+    state: &mut SearchState<W, H>,
     current_player: Player,
-    next_player: Player,
     depth: usize,
-    initial_score: i32,
-    better_score: impl Fn(i32, i32) -> bool + 'static + Send + Sync,
+    objective: Objective,
 ) -> (Option<usize>, i32) {
-    let mut best_score = initial_score;
+    let mut best_score = objective.initial_score();
     let mut best_col = None;
 
-    debug_assert_ne!(gameboard.available_columns_iter().count(), 0);
+    debug_assert_ne!(gameboard.legal_move_count(), 0);
 
-    // Inserts the player coin, updates the field, and performs a recursive
-    // search for following moves.
-    let simulate_move = |gameboard: &Gameboard<W, H>, col: usize| {
-        let mut gameboard_clone = gameboard.clone();
-        gameboard_clone
-            .insert_player_chip(col, current_player)
-            .unwrap();
+    // Inserts the player coin, performs a recursive search for following
+    // moves, and takes the coin back, so that the board doesn't need to be
+    // cloned for each move.
+    let simulate_move = |gameboard: &mut Gameboard<W, H>,
+                         col: usize,
+                         state: &mut SearchState<W, H>| {
+        let row = gameboard.insert_returning_row(col, current_player).unwrap();
 
         // skip col here, we take the col from the top level
         let (_, score) =
-            minmax_search_recursive(gameboard_clone, target_player, next_player, depth + 1);
+            minmax_search_recursive(gameboard, ctx, state, current_player.opponent(), depth + 1);
+        gameboard.remove_chip_at(row, col);
         (col, score)
     };
 
-    // top level: parallelize work
-    if depth == 0 {
-        let reduced = gameboard
+    // top level and large subtrees: parallelize work
+    if ctx.is_parallel_at(depth) {
+        // Each task uses its own state, which can read the tables of this
+        // one; they are merged afterwards. The results are reduced in column
+        // order, so ties are broken exactly like in the sequential search:
+        // the leftmost column wins.
+        let results = gameboard
             .available_columns_iter()
+            .filter(|col| depth > 0 || ctx.root_moves.is_none_or(|moves| moves.contains(col)))
             // rayon wants an owned collection
             .collect::<Vec<_>>()
             .into_par_iter()
-            .map(|col| simulate_move(gameboard, col))
-            .reduce(
-                || (usize::MAX, initial_score),
-                |acc, (col, score)| {
-                    if better_score(score, acc.1) {
-                        (col, score)
-                    } else {
-                        acc
-                    }
-                },
-            );
-        best_score = reduced.1;
-        best_col = Some(reduced.0);
+            .map(|col| {
+                // Tasks need independent boards.
+                let mut task_board = gameboard.clone();
+                let mut task_state = state.task_state();
+                let (col, score) = simulate_move(&mut task_board, col, &mut task_state);
+                (col, score, task_state.into_owned())
+            })
+            .collect::<Vec<_>>();
+
+        for (col, score, task_state) in results {
+            state.merge(task_state);
+            if objective.is_better(score, best_score) {
+                best_score = score;
+                best_col = Some(col);
+            }
+        }
     }
     // Normal recursion
     else {
-        for col in gameboard.available_columns_iter() {
-            let (_, score) = simulate_move(gameboard, col);
+        for col in gameboard
+            .available_columns_iter()
+            .filter(|col| depth > 0 || ctx.root_moves.is_none_or(|moves| moves.contains(col)))
+        {
+            let (_, score) = simulate_move(gameboard, col, state);
 
-            if better_score(score, best_score) {
+            if objective.is_better(score, best_score) {
                 best_score = score;
                 best_col = Some(col);
             }
@@ -83,96 +340,536 @@ fn search_best_move_in_depth<const W: usize, const H: usize>(
 /// - Multi-threaded: 9
 pub const MAX_DEPTH: usize = 9;
 
-/// Should be more than MAX_DEPTH.
-pub const SCORE_FACTOR: i32 = MAX_DEPTH as i32 + 1;
+/// Max depth of searches with evaluated leaves, see
+/// [`SearchConfig::evaluate_leaves`]. Takes about as long as a search to
+/// [`MAX_DEPTH`] without evaluated leaves for the midgame benchmarks.
+pub const EVALUATED_MAX_DEPTH: usize = MAX_DEPTH - 1;
+
+/// Default for the number of plies that must remain below a node so that its
+/// moves are searched in parallel: only the root is split.
+///
+/// Entries that a task stores are invisible to its sibling tasks, so
+/// splitting below the root didn't pay off for the midgame benchmarks.
+const PARALLEL_MIN_REMAINING_DEPTH: usize = usize::MAX;
+
+/// Bound of the heuristic scores of positions at the depth limit, see
+/// [`SearchConfig::evaluate_leaves`].
+const MAX_EVAL: i32 = 10_000;
+
+/// Should be more than the depth of the deepest nodes, `MAX_DEPTH + 1`.
+/// Searches with a custom depth derive their factor from that depth instead.
+pub const SCORE_FACTOR: i32 = MAX_DEPTH as i32 + 2;
 
 /// Recursive helper for [`minmax_search_recursive`].
+///
+/// `gameboard` is modified during the search but restored before returning.
 fn minmax_search_recursive<const W: usize, const H: usize>(
-    gameboard: Gameboard<W, H>,
-    target_player: Player,
+    gameboard: &mut Gameboard<W, H>,
+    ctx: SearchContext<W, H>,
+    state: &mut SearchState<W, H>,
     current_player: Player,
     depth: usize,
 ) -> (
     Option<usize>, /* move: col */
     i32,           /* score: pos: moves leading to win, neg: moves leading to loss */
 ) {
+    let target_player = ctx.target_player;
+    let score_factor = ctx.score_factor();
+    state.nodes += 1;
+
+    // Out of budget: the result is discarded by the caller anyway.
+    if state.nodes > ctx.max_nodes {
+        return (None, 0);
+    }
+
     // We start with the recursion tail: Can we stop the recursion?
     {
+        // Only the player who just moved can have won, with the move that
+        // led to this node, i.e., `depth` plies after the root.
+        let previous_player = current_player.opponent();
+
         // Target player wins
-        if target_player == current_player && gameboard.check_for_winner(target_player) {
+        if previous_player == target_player && gameboard.check_for_winner(target_player) {
             // schneller Sieg besser
             return (
                 None, /* upper level knows col */
-                SCORE_FACTOR - depth as i32,
+                score_factor - depth as i32,
             );
         }
         // Opponent wins
-        else if target_player != current_player && gameboard.check_for_winner(current_player) {
+        else if previous_player != target_player && gameboard.check_for_winner(previous_player) {
             // späte Niederlage "weniger schlimm"
             return (
                 None, /* upper level knows col */
-                -SCORE_FACTOR + depth as i32,
+                -score_factor + depth as i32,
+            );
+        }
+        // Target player reached the custom goal, which doesn't end the game
+        // at the root, as the root still has to pick one of its legal moves
+        else if depth > 0
+            && (ctx.is_goal)(gameboard)
+            && !gameboard.check_for_winner(target_player.opponent())
+        {
+            return (
+                None, /* upper level knows col */
+                score_factor - depth as i32,
             );
         }
         // draw
-        else if gameboard.gameover() {
-            return (None /* upper level knows col */, 0);
+        else if ctx.is_draw(gameboard, depth) {
+            return (None /* upper level knows col */, ctx.draw_score);
         }
     }
 
     // Abort. Too deep. Already takes quite some time with 7x6 fields..
-    if depth > MAX_DEPTH {
-        // TODO room for improvement: evaluate board, e.g., look for chains of three or so!
-        return (None /* upper level knows col */, 0);
+    if depth > ctx.max_depth {
+        return (
+            None, /* upper level knows col */
+            ctx.leaf_score(gameboard),
+        );
     }
 
-    if current_player == target_player {
-        search_best_move_in_depth(
-            &gameboard,
-            target_player,
-            current_player,
-            current_player.opponent(),
-            depth,
-            i32::MIN,
-            |new, best| new > best,
-        )
+    // The table stores scores from the perspective of the side to move.
+    let sign = if current_player == target_player {
+        1
     } else {
-        search_best_move_in_depth(
-            &gameboard,
-            target_player,
-            current_player,
-            current_player.opponent(),
-            depth,
-            i32::MAX,
-            |new, best| new < best,
-        )
+        -1
+    };
+    // Scores only depend on the remaining depth, so entries can be shared
+    // between searches with different depths.
+    let remaining_depth = ctx.max_depth - depth;
+    if let Some((best_col, score)) = state.lookup(gameboard, current_player, remaining_depth) {
+        return (best_col, sign * score);
+    }
+
+    let objective = if current_player == target_player {
+        Objective::Max
+    } else {
+        Objective::Min
+    };
+    let (best_col, score) =
+        search_best_move_in_depth(gameboard, ctx, state, current_player, depth, objective);
+
+    state.table.insert(
+        gameboard,
+        current_player,
+        remaining_depth,
+        (best_col, sign * score),
+    );
+    (best_col, score)
+}
+
+/// Searches the best move of the target player of `ctx`, who is to move.
+///
+/// Returns `None` if the game is already over.
+fn search_root<const W: usize, const H: usize>(
+    mut gameboard: Gameboard<W, H>,
+    ctx: SearchContext<W, H>,
+    state: &mut SearchState<W, H>,
+) -> Option<usize> {
+    // The recursion only detects wins of the player who moved last.
+    if gameboard.has_any_winner() {
+        return None;
     }
+    minmax_search_recursive(&mut gameboard, ctx, state, ctx.target_player, 0).0
 }
 
 /// Performs a recursive MinMax search from the given board state.
 ///
+/// Returns `None` if the game is already over.
+///
 /// At each step:
 /// - Checks for terminal conditions (win, loss, draw) and assigns scores.
-/// - Stops recursion at [`MAX_DEPTH`].
+/// - Stops recursion at [`EVALUATED_MAX_DEPTH`] and scores the position
+///   there with [`Gameboard::evaluate`], see [`minmax_search_depth`] for a
+///   custom depth.
 /// - Chooses the best move depending on whether the current player is
 ///   maximizing or minimizing the score.
 #[must_use]
 pub fn minmax_search<const W: usize, const H: usize>(
     gameboard: Gameboard<W, H>,
     current_player: Player,
-) -> usize {
-    minmax_search_recursive(gameboard, current_player, current_player, 0)
-        .0
-        .expect("should have legal move")
+) -> Option<usize> {
+    minmax_search_with_table(gameboard, current_player, &mut TranspositionTable::new())
+}
+
+/// Performs a MinMax search from the given board state but stops the
+/// recursion at `max_depth` instead of [`EVALUATED_MAX_DEPTH`].
+///
+/// Deeper searches play stronger but take exponentially more time. Returns
+/// `None` if the game is already over.
+#[must_use]
+pub fn minmax_search_depth<const W: usize, const H: usize>(
+    gameboard: Gameboard<W, H>,
+    current_player: Player,
+    max_depth: usize,
+) -> Option<usize> {
+    let config = SearchConfig {
+        max_depth,
+        ..SearchConfig::default()
+    };
+    search_with_config(gameboard, current_player, &config)
+}
+
+/// Performs a MinMax search from the given board state with the options of
+/// `config`.
+///
+/// Returns `None` if the game is already over.
+#[must_use]
+pub fn search_with_config<const W: usize, const H: usize>(
+    gameboard: Gameboard<W, H>,
+    current_player: Player,
+    config: &SearchConfig,
+) -> Option<usize> {
+    let ctx = SearchContext::with_config(current_player, config);
+    let mut state = SearchState::new(TranspositionTable::new());
+    search_root(gameboard, ctx, &mut state)
+}
+
+/// Searches the best move of `player` with iterative deepening until the
+/// search visited `max_nodes` positions, so that the think time is about the
+/// same for all positions.
+///
+/// Returns the best move of the deepest search that completed within the
+/// budget, or the first legal move if not even the shallowest one did.
+/// Returns `None` if the game is already over.
+#[must_use]
+pub fn search_best_move_node_limited<const W: usize, const H: usize>(
+    gameboard: Gameboard<W, H>,
+    player: Player,
+    max_nodes: u64,
+) -> Option<usize> {
+    if gameboard.has_any_winner() {
+        return None;
+    }
+
+    let mut best_col = gameboard.available_columns_iter().next();
+    let mut nodes = 0;
+    for max_depth in 0..=MAX_DEPTH {
+        let ctx = SearchContext {
+            max_depth,
+            // the budget is shared by all tasks
+            parallel: false,
+            max_nodes: max_nodes - nodes,
+            ..SearchContext::new(player)
+        };
+        let mut state = SearchState::new(TranspositionTable::new());
+        let col = search_root(gameboard.clone(), ctx, &mut state);
+        if state.nodes > ctx.max_nodes {
+            break;
+        }
+        nodes += state.nodes;
+        best_col = col;
+
+        // deeper searches can't find anything new
+        if max_depth >= gameboard.remaining_moves() {
+            break;
+        }
+    }
+    best_col
+}
+
+/// Half width of the aspiration window around the score of the previous
+/// iteration, see [`search_best_move_aspiration`].
+const ASPIRATION_DELTA: i32 = 2;
+
+/// Alpha-beta variant of [`minmax_search_recursive`]: scores inside the
+/// window `(alpha, beta)` are exact, a score `<= alpha` is an upper bound
+/// and a score `>= beta` a lower bound of the real score.
+///
+/// Searches sequentially and without a transposition table, as the table
+/// only stores exact scores.
+fn alpha_beta_recursive<const W: usize, const H: usize>(
+    gameboard: &Gameboard<W, H>,
+    ctx: SearchContext<W, H>,
+    nodes: &mut u64,
+    current_player: Player,
+    depth: usize,
+    (mut alpha, mut beta): (i32, i32),
+) -> (Option<usize>, i32) {
+    let target_player = ctx.target_player;
+    let score_factor = ctx.score_factor();
+    *nodes += 1;
+
+    // same recursion tail as in `minmax_search_recursive`
+    let previous_player = current_player.opponent();
+    if previous_player == target_player && gameboard.check_for_winner(target_player) {
+        return (None, score_factor - depth as i32);
+    } else if previous_player != target_player && gameboard.check_for_winner(previous_player) {
+        return (None, -score_factor + depth as i32);
+    } else if depth > 0
+        && (ctx.is_goal)(gameboard)
+        && !gameboard.check_for_winner(target_player.opponent())
+    {
+        return (None, score_factor - depth as i32);
+    } else if ctx.is_draw(gameboard, depth) {
+        return (None, ctx.draw_score);
+    } else if depth > ctx.max_depth {
+        return (None, ctx.leaf_score(gameboard));
+    }
+
+    let objective = if current_player == target_player {
+        Objective::Max
+    } else {
+        Objective::Min
+    };
+    let mut best_score = objective.initial_score();
+    let mut best_col = None;
+    for col in gameboard
+        .available_columns_iter()
+        .filter(|col| depth > 0 || ctx.root_moves.is_none_or(|moves| moves.contains(col)))
+    {
+        let mut gameboard_clone = gameboard.clone();
+        gameboard_clone
+            .insert_player_chip(col, current_player)
+            .unwrap();
+        let (_, score) = alpha_beta_recursive(
+            &gameboard_clone,
+            ctx,
+            nodes,
+            current_player.opponent(),
+            depth + 1,
+            (alpha, beta),
+        );
+
+        if objective.is_better(score, best_score) {
+            best_score = score;
+            best_col = Some(col);
+        }
+        match objective {
+            Objective::Max => alpha = alpha.max(score),
+            Objective::Min => beta = beta.min(score),
+        }
+        // the opponent avoids this node anyway
+        if alpha >= beta {
+            break;
+        }
+    }
+    (best_col, best_score)
+}
+
+/// Iterative deepening with alpha-beta searches up to `max_depth`, optionally
+/// with aspiration windows. Returns the best move and score of the deepest
+/// search and the number of visited nodes of all searches.
+fn alpha_beta_deepening<const W: usize, const H: usize>(
+    gameboard: &Gameboard<W, H>,
+    player: Player,
+    max_depth: usize,
+    aspiration: bool,
+) -> (Option<usize>, i32, u64) {
+    let mut nodes = 0;
+    let mut best_col = None;
+    let mut previous_score = None;
+    for depth in 0..=max_depth {
+        let ctx = SearchContext {
+            max_depth: depth,
+            parallel: false,
+            ..SearchContext::new(player)
+        };
+        let full_window = (i32::MIN, i32::MAX);
+        let (alpha, beta) = match previous_score {
+            Some(score) if aspiration => (score - ASPIRATION_DELTA, score + ASPIRATION_DELTA),
+            _ => full_window,
+        };
+
+        let (mut col, mut score) =
+            alpha_beta_recursive(gameboard, ctx, &mut nodes, player, 0, (alpha, beta));
+        // the score is only a bound: search again without restrictions
+        if score <= alpha || score >= beta {
+            (col, score) = alpha_beta_recursive(gameboard, ctx, &mut nodes, player, 0, full_window);
+        }
+        best_col = col;
+        previous_score = Some(score);
+
+        // deeper searches can't find anything new
+        if depth >= gameboard.remaining_moves() {
+            break;
+        }
+    }
+    (best_col, previous_score.unwrap_or(0), nodes)
+}
+
+/// Searches the best move of `player` with iterative deepening up to
+/// `max_depth` and alpha-beta pruning.
+///
+/// Each iteration starts with a narrow aspiration window around the score of
+/// the previous one, which prunes more nodes as long as the score is stable
+/// across depths. If the score falls outside of the window, the iteration is
+/// repeated with the full window. Returns `None` if the game is already
+/// over.
+#[must_use]
+pub fn search_best_move_aspiration<const W: usize, const H: usize>(
+    gameboard: Gameboard<W, H>,
+    player: Player,
+    max_depth: usize,
+) -> Option<usize> {
+    if gameboard.has_any_winner() {
+        return None;
+    }
+    alpha_beta_deepening(&gameboard, player, max_depth, true).0
+}
+
+/// Performs a MinMax search from the given board state and memoizes results
+/// in the given table, which can be reused across searches.
+///
+/// Returns `None` if the game is already over.
+#[must_use]
+pub fn minmax_search_with_table<const W: usize, const H: usize>(
+    gameboard: Gameboard<W, H>,
+    current_player: Player,
+    table: &mut TranspositionTable<W, H>,
+) -> Option<usize> {
+    let ctx = SearchContext::with_config(current_player, &SearchConfig::default());
+    let mut state = SearchState::new(core::mem::take(table));
+    let best_col = search_root(gameboard, ctx, &mut state);
+    *table = state.table;
+    best_col
+}
+
+/// Performs a MinMax search like [`minmax_search`] but only considers the
+/// given columns at the root.
+///
+/// Returns `None` if none of the columns is a legal move.
+#[must_use]
+pub fn minmax_search_restricted<const W: usize, const H: usize>(
+    gameboard: Gameboard<W, H>,
+    current_player: Player,
+    allowed: &[usize],
+) -> Option<usize> {
+    if !gameboard
+        .available_columns_iter()
+        .any(|col| allowed.contains(&col))
+    {
+        return None;
+    }
+
+    let ctx = SearchContext {
+        root_moves: Some(allowed),
+        ..SearchContext::with_config(current_player, &SearchConfig::default())
+    };
+    let mut state = SearchState::new(TranspositionTable::new());
+    search_root(gameboard, ctx, &mut state)
+}
+
+/// Returns the number of plies until the game is decided from the
+/// perspective of `player`, derived from the MinMax score.
+///
+/// A positive value is the distance to a forced win, a negative value the
+/// distance to a forced loss. `None` means that the position is a draw or
+/// that the outcome is beyond the search depth.
+#[must_use]
+pub fn distance_to_win<const W: usize, const H: usize>(
+    mut gameboard: Gameboard<W, H>,
+    player: Player,
+) -> Option<i32> {
+    let ctx = SearchContext::new(player);
+    let mut state = SearchState::new(TranspositionTable::new());
+    let (_, score) = minmax_search_recursive(&mut gameboard, ctx, &mut state, player, 0);
+    score_to_distance(score)
+}
+
+/// Converts a MinMax score into the distance to a forced win (positive) or
+/// loss (negative). Returns `None` for undecided positions.
+const fn score_to_distance(score: i32) -> Option<i32> {
+    match score {
+        0 => None,
+        score if score > 0 => Some(SCORE_FACTOR - score),
+        score => Some(-(SCORE_FACTOR + score)),
+    }
+}
+
+/// Searches for the best move of `player` for a puzzle-like objective.
+///
+/// Positions for which `is_goal` returns `true` count as a win for `player`,
+/// in addition to the regular winning condition. A goal that already holds
+/// at the root doesn't end the search. Returns `None` if there is no legal
+/// move.
+#[must_use]
+pub fn search_with_objective<const W: usize, const H: usize>(
+    gameboard: Gameboard<W, H>,
+    player: Player,
+    is_goal: impl Fn(&Gameboard<W, H>) -> bool + Sync,
+) -> Option<usize> {
+    let ctx = SearchContext {
+        is_goal: &is_goal,
+        ..SearchContext::new(player)
+    };
+    // The goal might not be symmetric.
+    let mut state = SearchState::new(TranspositionTable::without_symmetry());
+    search_root(gameboard, ctx, &mut state)
+}
+
+/// Searches every legal move of `player` at the root position and reports
+/// `(column, score, nodes)` for each of them, where `nodes` is the number of
+/// positions visited to compute the score.
+///
+/// This helps to understand why the AI picks a move.
+#[must_use]
+pub fn root_move_report<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    player: Player,
+) -> Vec<(usize, i32, u64)> {
+    let ctx = SearchContext::new(player);
+
+    game.board()
+        .available_columns_iter()
+        .collect::<Vec<_>>()
+        .into_par_iter()
+        .map(|col| {
+            let mut gameboard = game.board().clone();
+            gameboard.insert_player_chip(col, player).unwrap();
+
+            let mut state = SearchState::new(TranspositionTable::new());
+            let (_, score) =
+                minmax_search_recursive(&mut gameboard, ctx, &mut state, player.opponent(), 1);
+            (col, score, state.nodes)
+        })
+        .collect()
+}
+
+/// Returns the outcome of every column for `player`, e.g., to draw a heatmap.
+///
+/// Each legal column holds the distance to a forced win (positive), the
+/// distance to a forced loss (negative), or `0` if the search doesn't find a
+/// decision. Illegal columns are `None`.
+#[must_use]
+pub fn move_outcome_map<const W: usize, const H: usize>(
+    game: &Game<W, H>,
+    player: Player,
+) -> [Option<i32>; W] {
+    let mut map = [None; W];
+    for (col, score, _) in root_move_report(game, player) {
+        map[col] = Some(score_to_distance(score).unwrap_or(0));
+    }
+    map
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::minmax::minmax_search;
-    use crate::{Gameboard, Player};
+    use crate::minmax::{
+        SearchContext, SearchState, alpha_beta_deepening, distance_to_win, minmax_search,
+        minmax_search_depth, minmax_search_recursive, minmax_search_with_table, move_outcome_map,
+        root_move_report, search_best_move_aspiration, search_best_move_node_limited, search_root,
+        search_with_objective,
+    };
+    use crate::{
+        EVALUATED_MAX_DEPTH, Game, Gameboard, MAX_DEPTH, Player, SearchConfig, TranspositionTable,
+        midgame_positions, search_best_move, search_with_config,
+    };
+    use alloc::vec::Vec;
 
-    #[test]
-    fn test_minmax() {
+    /// Config of a search that doesn't evaluate the leaves, i.e., that
+    /// only tells forced wins and losses apart from undecided positions.
+    fn plain_config(max_depth: usize) -> SearchConfig {
+        SearchConfig {
+            max_depth,
+            evaluate_leaves: false,
+            ..SearchConfig::default()
+        }
+    }
+
+    /// Board where [`Player::Player1`] wins by playing column 2.
+    fn one_move_win_board() -> Gameboard<4, 4> {
         let mut board = Gameboard::<4, 4>::new();
         board.insert_player_chip(0, Player::Player1).unwrap();
         board.insert_player_chip(0, Player::Player2).unwrap();
@@ -192,7 +889,490 @@ mod tests {
         board.insert_player_chip(3, Player::Player2).unwrap();
         board.insert_player_chip(3, Player::Player2).unwrap();
 
+        board
+    }
+
+    #[test]
+    fn test_minmax() {
+        let board = one_move_win_board();
         let best_move = minmax_search(board, Player::Player1);
-        assert_eq!(best_move, 2);
+        assert_eq!(best_move, Some(2));
+    }
+
+    #[test]
+    fn test_minmax_game_over() {
+        let mut board = one_move_win_board();
+        board.insert_player_chip(2, Player::Player1).unwrap();
+        assert_eq!(minmax_search(board.clone(), Player::Player2), None);
+        assert_eq!(minmax_search(board.clone(), Player::Player1), None);
+
+        // fill the board
+        board.insert_player_chip(3, Player::Player1).unwrap();
+        assert!(board.gameover());
+        assert_eq!(minmax_search(board, Player::Player2), None);
+
+        let mut game = Game::<4, 4>::new();
+        for col in [0, 1, 0, 1, 0, 1, 0] {
+            game.insert_player_chip(col, game.turn()).unwrap();
+        }
+        assert_eq!(search_best_move(&game, game.turn()), None);
+    }
+
+    #[test]
+    fn test_distance_to_win() {
+        let board = one_move_win_board();
+        assert_eq!(distance_to_win(board.clone(), Player::Player1), Some(1));
+        assert_eq!(
+            board.winning_moves_by_speed(Player::Player1).first(),
+            Some(&(2, 1))
+        );
+
+        assert_eq!(
+            distance_to_win(Gameboard::<4, 4>::new(), Player::Player1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_search_with_objective() {
+        let board = Gameboard::<4, 4>::new();
+        let best_move = search_with_objective(board.clone(), Player::Player1, |board| {
+            board.board()[0][0] == Some(Player::Player1)
+        });
+        assert_eq!(best_move, Some(0));
+
+        let best_move = search_with_objective(board, Player::Player2, |board| {
+            board.board()[0][3] == Some(Player::Player2)
+        });
+        assert_eq!(best_move, Some(3));
+
+        // the goal already holds at the root
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(1, Player::Player2).unwrap();
+        let best_move = search_with_objective(board, Player::Player1, |board| {
+            board.board()[0][0] == Some(Player::Player1)
+        });
+        assert_eq!(best_move, Some(0));
+    }
+
+    #[test]
+    fn test_transposition_table_mirror() {
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(1, Player::Player2).unwrap();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        board.insert_player_chip(1, Player::Player2).unwrap();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        let mirrored = board.mirrored();
+
+        let mut table = TranspositionTable::new();
+        let best_move =
+            minmax_search_with_table(board.clone(), Player::Player2, &mut table).unwrap();
+        assert_eq!(best_move, 0);
+        assert_eq!(Some(best_move), minmax_search(board, Player::Player2));
+
+        let len = table.len();
+        let hits = table.hits();
+        assert!(len > 0);
+
+        let best_move_mirrored = minmax_search_with_table(mirrored, Player::Player2, &mut table);
+        assert_eq!(best_move_mirrored, Some(3 - best_move));
+        // the mirrored position is found in the table right away
+        assert_eq!(table.hits(), hits + 1);
+        assert_eq!(table.len(), len);
+    }
+
+    #[test]
+    fn test_transposition_table_reuse() {
+        // Both positions reach the same positions two plies later.
+        let mut first = Gameboard::<7, 6>::new();
+        first.insert_player_chip(0, Player::Player1).unwrap();
+        first.insert_player_chip(1, Player::Player2).unwrap();
+        let mut second = Gameboard::<7, 6>::new();
+        second.insert_player_chip(2, Player::Player1).unwrap();
+        second.insert_player_chip(1, Player::Player2).unwrap();
+
+        let search = |board, table| {
+            let ctx = SearchContext {
+                max_depth: 5,
+                ..SearchContext::new(Player::Player1)
+            };
+            let mut state = SearchState::new(table);
+            let col = search_root(board, ctx, &mut state);
+            (col, state.nodes, state.table)
+        };
+
+        let (_, _, table) = search(first, TranspositionTable::new());
+        let (fresh_col, fresh_nodes, _) = search(second.clone(), TranspositionTable::new());
+        let (col, nodes, table) = search(second, table);
+        assert_eq!(col, fresh_col);
+        // the parallel tasks below the root find the shared positions
+        assert!(nodes < fresh_nodes);
+        assert!(table.hits() > 1);
+    }
+
+    #[test]
+    fn test_transposition_table_scoring_height() {
+        // Column 0 only wins if the top row counts, column 4 wins anyway.
+        let mut full = Gameboard::<5, 5>::new();
+        let mut reduced = Gameboard::<5, 5>::with_scoring_height(4).unwrap();
+        for (col, player) in [
+            (0, Player::Player2),
+            (0, Player::Player1),
+            (0, Player::Player1),
+            (0, Player::Player1),
+            (1, Player::Player1),
+            (2, Player::Player1),
+            (3, Player::Player1),
+        ] {
+            full.insert_player_chip(col, player).unwrap();
+            reduced.insert_player_chip(col, player).unwrap();
+        }
+
+        let mut table = TranspositionTable::new();
+        let best_move = minmax_search_with_table(full, Player::Player1, &mut table);
+        assert_eq!(best_move, Some(0));
+        let best_move = minmax_search_with_table(reduced, Player::Player1, &mut table);
+        assert_eq!(best_move, Some(4));
+    }
+
+    #[test]
+    fn test_transposition_table_capacity() {
+        let board = one_move_win_board();
+        let mut table = TranspositionTable::with_capacity(3);
+        let best_move = minmax_search_with_table(board.clone(), Player::Player2, &mut table);
+        assert_eq!(best_move, minmax_search(board, Player::Player2));
+        assert!(table.len() <= 3);
+        assert_eq!(table.capacity(), 3);
+
+        let mut board = Gameboard::<4, 4>::new();
+        board.insert_player_chip(0, Player::Player1).unwrap();
+        let mut table = TranspositionTable::with_capacity(0);
+        let best_move = minmax_search_with_table(board.clone(), Player::Player2, &mut table);
+        assert_eq!(best_move, minmax_search(board, Player::Player2));
+        assert!(table.is_empty());
+    }
+
+    #[test]
+    fn test_minmax_fixtures() {
+        // (moves so far, best move, distance to win); results of the search
+        // before the comparator closure was replaced by `Objective`, except
+        // for the last two: the search only sees the loss in 10 plies and
+        // the win in 9 plies since wins count in the ply they are made
+        let fixtures: [(&[usize], usize, Option<i32>); 4] = [
+            (&[1, 2, 1, 2], 0, None),
+            (&[0, 0, 3, 3, 1], 2, None),
+            (&[1, 1, 2, 2, 0, 3], 1, None),
+            (&[0, 1, 2, 3, 3, 2, 1], 1, Some(9)),
+        ];
+
+        for (moves, best_move, distance) in fixtures {
+            let mut board = Gameboard::<4, 4>::new();
+            let mut player = Player::Player1;
+            for &col in moves {
+                board.insert_player_chip(col, player).unwrap();
+                player = player.opponent();
+            }
+
+            assert_eq!(
+                search_with_config(board.clone(), player, &plain_config(MAX_DEPTH)),
+                Some(best_move)
+            );
+            assert_eq!(distance_to_win(board, player), distance);
+        }
+    }
+
+    #[test]
+    fn test_root_move_report() {
+        let mut game = Game::<4, 4>::new();
+        let mut player = Player::Player1;
+        for col in [0, 0, 3, 3, 1] {
+            game.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+
+        let report = root_move_report(&game, player);
+        assert_eq!(report.len(), 4);
+        assert!(report.iter().all(|&(_, _, nodes)| nodes > 0));
+
+        let best = report.iter().fold(
+            report[0],
+            |best, &entry| if entry.1 > best.1 { entry } else { best },
+        );
+        assert_eq!(Some(best.0), search_best_move(&game, player));
+    }
+
+    #[test]
+    fn test_parallel_search_matches_sequential() {
+        let mut boards = midgame_positions()
+            .into_iter()
+            .map(|game| (game.board().clone(), game.turn()))
+            .collect::<Vec<_>>();
+        boards.push((Gameboard::new(), Player::Player1));
+
+        for (board, player) in boards {
+            let search = |parallel| {
+                let ctx = SearchContext {
+                    max_depth: 5,
+                    parallel,
+                    ..SearchContext::new(player)
+                };
+                let mut state = SearchState::new(TranspositionTable::new());
+                minmax_search_recursive(&mut board.clone(), ctx, &mut state, player, 0)
+            };
+            assert_eq!(search(true), search(false));
+        }
+
+        let board = one_move_win_board();
+        for allowed in [&[2, 3][..], &[3]] {
+            let search = |parallel| {
+                let ctx = SearchContext {
+                    root_moves: Some(allowed),
+                    parallel,
+                    ..SearchContext::new(Player::Player1)
+                };
+                let mut state = SearchState::new(TranspositionTable::new());
+                minmax_search_recursive(&mut board.clone(), ctx, &mut state, Player::Player1, 0)
+            };
+            assert_eq!(search(true), search(false));
+        }
+    }
+
+    #[test]
+    fn test_search_restores_board() {
+        for game in midgame_positions() {
+            let mut board = game.board().clone();
+            let ctx = SearchContext {
+                max_depth: 6,
+                parallel: false,
+                ..SearchContext::new(game.turn())
+            };
+            let mut state = SearchState::new(TranspositionTable::new());
+            minmax_search_recursive(&mut board, ctx, &mut state, game.turn(), 0);
+            // compares the cells and the mask of legal moves
+            assert_eq!(&board, game.board());
+        }
+    }
+
+    #[test]
+    fn test_parallel_min_remaining_depth() {
+        let game = &midgame_positions()[0];
+        let board = game.board().clone();
+        let player = game.turn();
+        let root_moves = board.legal_move_count() as u64;
+
+        let search = |parallel, parallel_min_remaining_depth| {
+            let ctx = SearchContext {
+                max_depth: 5,
+                parallel,
+                parallel_min_remaining_depth,
+                ..SearchContext::new(player)
+            };
+            let mut state = SearchState::new(TranspositionTable::new());
+            let result = minmax_search_recursive(&mut board.clone(), ctx, &mut state, player, 0);
+            (result, state.tasks)
+        };
+
+        let (sequential, tasks) = search(false, 0);
+        assert_eq!(tasks, 0);
+
+        // only the root is split
+        let (result, tasks) = search(true, usize::MAX);
+        assert_eq!(result, sequential);
+        assert_eq!(tasks, root_moves);
+
+        // the nodes below the root are split as well
+        let (result, tasks) = search(true, 4);
+        assert_eq!(result, sequential);
+        assert!(tasks > root_moves);
+
+        // splitting every level spawns even more tasks
+        let (result, more_tasks) = search(true, 0);
+        assert_eq!(result, sequential);
+        assert!(more_tasks > tasks);
+    }
+
+    #[test]
+    fn test_search_with_config() {
+        let game = &midgame_positions()[0];
+        let expected = minmax_search_depth(game.board().clone(), game.turn(), 4);
+        for parallel_min_remaining_depth in [0, 3, usize::MAX] {
+            let config = SearchConfig {
+                max_depth: 4,
+                parallel_min_remaining_depth,
+                ..SearchConfig::default()
+            };
+            assert_eq!(
+                search_with_config(game.board().clone(), game.turn(), &config),
+                expected
+            );
+        }
+        assert_eq!(
+            SearchConfig::default().parallel_min_remaining_depth,
+            usize::MAX
+        );
+    }
+
+    #[test]
+    fn test_aspiration_search() {
+        for (i, game) in midgame_positions().into_iter().enumerate() {
+            let board = game.board();
+            let player = game.turn();
+            let (col, score, nodes) = alpha_beta_deepening(board, player, 6, true);
+            let (full_col, full_score, full_nodes) = alpha_beta_deepening(board, player, 6, false);
+
+            // pruning doesn't change the result of the search
+            let ctx = SearchContext {
+                max_depth: 6,
+                ..SearchContext::new(player)
+            };
+            let mut state = SearchState::new(TranspositionTable::new());
+            let (_, minmax_score) =
+                minmax_search_recursive(&mut board.clone(), ctx, &mut state, player, 0);
+            assert_eq!((col, score), (full_col, full_score));
+            assert_eq!(score, minmax_score);
+            assert_eq!(search_best_move_aspiration(board.clone(), player, 6), col);
+
+            // the score of the first position is 0 at every depth, so the
+            // narrow windows never fail
+            assert!(nodes <= full_nodes);
+            if i == 0 {
+                assert!(nodes < full_nodes);
+            }
+        }
+
+        let mut board = Gameboard::<4, 4>::new();
+        for col in [0, 1, 0, 1, 0, 1, 0] {
+            board.insert_player_chip(col, Player::Player1).unwrap();
+        }
+        assert_eq!(search_best_move_aspiration(board, Player::Player2, 6), None);
+    }
+
+    #[test]
+    fn test_move_outcome_map() {
+        let mut game = Game::<4, 4>::new();
+        // replay the chips row by row, so that they are stacked correctly
+        for row in one_move_win_board().rows_bottom_up() {
+            for (col, cell) in row.into_iter().enumerate() {
+                if let Some(player) = cell {
+                    game.insert_player_chip(col, player).unwrap();
+                }
+            }
+        }
+        assert_eq!(game.board(), &one_move_win_board());
+
+        // Column 2 wins right away; column 3 blocks the opponent, who then
+        // blocks column 2 and fills the board.
+        let map = move_outcome_map(&game, Player::Player1);
+        assert_eq!(map, [None, None, Some(1), Some(0)]);
+        assert_eq!(
+            game.board().winning_moves_by_speed(Player::Player1),
+            [(2, 1)]
+        );
+    }
+
+    #[test]
+    fn test_search_best_move_node_limited() {
+        let board = one_move_win_board();
+        let best_move = search_best_move_node_limited(board.clone(), Player::Player1, 1);
+        assert!(best_move.is_some_and(|col| board.available_columns_iter().any(|c| c == col)));
+        assert_eq!(
+            search_best_move_node_limited(board, Player::Player1, u64::MAX),
+            Some(2)
+        );
+
+        let mut board = Gameboard::<4, 4>::new();
+        let mut player = Player::Player1;
+        for col in [0, 0, 3, 3, 1] {
+            board.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+        assert_eq!(
+            search_best_move_node_limited(board.clone(), player, u64::MAX),
+            minmax_search(board.clone(), player)
+        );
+        assert!(search_best_move_node_limited(board, player, 10).is_some());
+    }
+
+    #[test]
+    fn test_minmax_search_depth() {
+        let mut board = Gameboard::<5, 5>::new();
+        let mut player = Player::Player1;
+        for col in [2, 0, 3, 4, 3, 3, 2, 3, 0, 0] {
+            board.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+
+        // The shallow search sees no difference between the moves, but the
+        // deeper search finds a forced win.
+        assert_eq!(
+            search_with_config(board.clone(), player, &plain_config(4)),
+            Some(0)
+        );
+        assert_eq!(
+            search_with_config(board.clone(), player, &plain_config(8)),
+            Some(2)
+        );
+        assert!(distance_to_win(board, player).is_some_and(|distance| distance > 0));
+    }
+
+    #[test]
+    fn test_search_with_draw_score() {
+        let mut board = Gameboard::<4, 4>::new();
+        let mut player = Player::Player1;
+        for col in [2, 2, 1, 1, 1, 1, 3, 0, 0, 0, 2, 3] {
+            board.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+
+        // Column 0 blocks the last open lines, so nobody can win anymore.
+        let mut simplified = board.clone();
+        simplified.insert_player_chip(0, player).unwrap();
+        assert!(simplified.is_dead_draw());
+        assert!(!board.is_dead_draw());
+        assert_eq!(
+            search_with_config(board.clone(), player, &plain_config(1)),
+            Some(0)
+        );
+
+        // With a penalty for draws, the AI keeps the game open instead.
+        let config = SearchConfig {
+            draw_score: -1,
+            ..plain_config(1)
+        };
+        let best_move = search_with_config(board.clone(), player, &config).unwrap();
+        let mut open = board;
+        open.insert_player_chip(best_move, player).unwrap();
+        assert!(!open.is_dead_draw());
+
+        // A dead draw at the root still has legal moves to pick from.
+        assert!(search_with_config(simplified, player.opponent(), &config).is_some());
+    }
+
+    #[test]
+    fn test_search_with_evaluated_leaves() {
+        let config = SearchConfig {
+            max_depth: 2,
+            evaluate_leaves: true,
+            ..SearchConfig::default()
+        };
+
+        // Without a forced result in sight, the plain search takes the
+        // leftmost column, while the evaluated search prefers the center.
+        let board = Gameboard::<7, 6>::new();
+        assert_eq!(
+            search_with_config(board.clone(), Player::Player1, &plain_config(2)),
+            Some(0)
+        );
+        assert_eq!(search_with_config(board, Player::Player1, &config), Some(3));
+
+        // a win still outranks every heuristic score
+        let board = one_move_win_board();
+        assert_eq!(search_with_config(board, Player::Player1, &config), Some(2));
+
+        // the AI evaluates the leaves
+        assert!(SearchConfig::default().evaluate_leaves);
+        assert_eq!(SearchConfig::default().max_depth, EVALUATED_MAX_DEPTH);
     }
 }
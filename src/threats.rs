@@ -0,0 +1,135 @@
+//! Threat-space search for forced wins.
+//!
+//! In contrast to the MinMax search, this search only follows moves of the
+//! attacker that create an immediate threat, i.e., moves the opponent is
+//! forced to answer. This keeps the search tree small enough to look much
+//! deeper.
+
+use crate::{Gameboard, Player};
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// OR node: the attacker needs a single move that forces the win.
+///
+/// Returns the moves of the attacker along the longest defense.
+fn attacker_moves<const W: usize, const H: usize>(
+    gameboard: &Gameboard<W, H>,
+    attacker: Player,
+    plies_left: usize,
+) -> Option<Vec<usize>> {
+    if plies_left == 0 {
+        return None;
+    }
+
+    if let Some(col) = gameboard
+        .available_columns_iter()
+        .find(|&col| gameboard.is_winning_move(col, attacker))
+    {
+        return Some(vec![col]);
+    }
+
+    // We need at least our move, the reply, and our winning move.
+    if plies_left < 3 {
+        return None;
+    }
+
+    gameboard.available_columns_iter().find_map(|col| {
+        let mut gameboard = gameboard.clone();
+        gameboard.insert_player_chip(col, attacker).unwrap();
+
+        // Only consider moves that create a threat the defender can't
+        // ignore, and that don't leave the defender with a win of their own.
+        let creates_threat = gameboard.in_check(attacker.opponent());
+        if !creates_threat || gameboard.in_check(attacker) || gameboard.gameover() {
+            return None;
+        }
+
+        let mut plan = defender_replies(&gameboard, attacker, plies_left - 1)?;
+        plan.insert(0, col);
+        Some(plan)
+    })
+}
+
+/// AND node: the attacker must force the win after every reply of the
+/// defender.
+///
+/// Returns the moves of the attacker along the longest defense.
+fn defender_replies<const W: usize, const H: usize>(
+    gameboard: &Gameboard<W, H>,
+    attacker: Player,
+    plies_left: usize,
+) -> Option<Vec<usize>> {
+    let mut longest: Option<Vec<usize>> = None;
+
+    for reply in gameboard.available_columns_iter() {
+        let mut gameboard = gameboard.clone();
+        gameboard
+            .insert_player_chip(reply, attacker.opponent())
+            .unwrap();
+
+        let plan = attacker_moves(&gameboard, attacker, plies_left - 1)?;
+        if longest
+            .as_ref()
+            .is_none_or(|longest| plan.len() > longest.len())
+        {
+            longest = Some(plan);
+        }
+    }
+
+    longest
+}
+
+/// Searches for a sequence of moves that forces a win for `player` within
+/// `max_plies` plies, counting the moves of both players.
+///
+/// Only moves that create immediate threats are considered, so this search
+/// can look much deeper than the MinMax search but doesn't find quiet
+/// winning moves. The returned moves are the moves of `player` along the
+/// strongest defense of the opponent. Returns `None` if no forced win was
+/// found.
+#[must_use]
+pub fn find_forced_win<const W: usize, const H: usize>(
+    gameboard: &Gameboard<W, H>,
+    player: Player,
+    max_plies: usize,
+) -> Option<Vec<usize>> {
+    attacker_moves(gameboard, player, max_plies)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::threats::find_forced_win;
+    use crate::{Gameboard, Player};
+    use alloc::vec;
+
+    #[test]
+    fn test_find_forced_win() {
+        let mut board = Gameboard::<7, 6>::new();
+        let mut player = Player::Player1;
+        for col in [3, 0, 2, 5, 3, 6, 6, 6, 6, 2] {
+            board.insert_player_chip(col, player).unwrap();
+            player = player.opponent();
+        }
+
+        assert_eq!(find_forced_win(&board, Player::Player1, 3), None);
+        let plan = find_forced_win(&board, Player::Player1, 5).unwrap();
+        assert_eq!(plan.len(), 3);
+        assert_eq!(plan[0], 1);
+
+        assert_eq!(
+            find_forced_win(&Gameboard::<7, 6>::new(), Player::Player1, 7),
+            None
+        );
+    }
+
+    #[test]
+    fn test_find_forced_win_immediate() {
+        let mut board = Gameboard::<7, 6>::new();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+        board.insert_player_chip(0, Player::Player2).unwrap();
+
+        assert_eq!(find_forced_win(&board, Player::Player2, 1), Some(vec![0]));
+        assert_eq!(find_forced_win(&board, Player::Player1, 5), None);
+    }
+}
@@ -0,0 +1,212 @@
+//! Transposition table to memoize MinMax search results.
+//!
+//! Positions are stored under their canonical form, i.e., the lexicographically
+//! smaller one of the board and its horizontal mirror image. This way, mirrored
+//! positions share the same entry.
+
+use crate::{Cell, Gameboard, Player};
+use alloc::collections::BTreeMap;
+
+/// Key of an entry: canonical board, side to move, remaining search depth,
+/// and scoring height, see [`Gameboard::scoring_height`].
+type Key<const W: usize, const H: usize> = ([[Cell; W]; H], Player, usize, usize);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Entry {
+    /// Best column in the canonical orientation.
+    best_col: Option<usize>,
+    /// Score from the perspective of the side to move.
+    score: i32,
+}
+
+/// Default for the maximum number of entries of a table, see
+/// [`TranspositionTable::with_capacity`].
+pub const DEFAULT_TABLE_CAPACITY: usize = 1 << 20;
+
+/// Memoizes the results of the MinMax search across positions and searches.
+///
+/// The table holds at most a fixed number of entries. Once it is full, the
+/// entries with the smallest remaining depth make room for new ones, as
+/// their subtrees are the cheapest to search again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TranspositionTable<const W: usize = 7, const H: usize = 6> {
+    entries: BTreeMap<Key<W, H>, Entry>,
+    hits: u64,
+    use_symmetry: bool,
+    capacity: usize,
+}
+
+impl<const W: usize, const H: usize> TranspositionTable<W, H> {
+    /// Creates a table with [`DEFAULT_TABLE_CAPACITY`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::with_capacity(DEFAULT_TABLE_CAPACITY)
+    }
+
+    /// Creates a table that holds at most `capacity` entries, e.g., to
+    /// bound the memory usage of long-lived tables.
+    #[must_use]
+    pub const fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            hits: 0,
+            use_symmetry: true,
+            capacity,
+        }
+    }
+
+    /// Creates a table that doesn't share entries between mirrored positions.
+    ///
+    /// This is required when the search objective isn't symmetric.
+    pub(crate) const fn without_symmetry() -> Self {
+        let mut table = Self::new();
+        table.use_symmetry = false;
+        table
+    }
+
+    /// Creates an empty table with the same configuration as `self`.
+    pub(crate) const fn empty_like(&self) -> Self {
+        Self {
+            entries: BTreeMap::new(),
+            hits: 0,
+            use_symmetry: self.use_symmetry,
+            capacity: self.capacity,
+        }
+    }
+
+    /// Returns the number of stored positions.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the maximum number of stored positions.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns how often a lookup found a stored position.
+    #[must_use]
+    pub const fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Returns the key of the position and whether the board was mirrored to
+    /// get there.
+    fn key(
+        &self,
+        gameboard: &Gameboard<W, H>,
+        side_to_move: Player,
+        remaining_depth: usize,
+    ) -> (Key<W, H>, bool) {
+        let board = *gameboard.board();
+        let scoring_height = gameboard.scoring_height();
+        if self.use_symmetry {
+            let mirrored = *gameboard.mirrored().board();
+            if mirrored < board {
+                return (
+                    (mirrored, side_to_move, remaining_depth, scoring_height),
+                    true,
+                );
+            }
+        }
+        (
+            (board, side_to_move, remaining_depth, scoring_height),
+            false,
+        )
+    }
+
+    /// Mirrors the column if `mirrored` is set.
+    const fn orient(col: usize, mirrored: bool) -> usize {
+        if mirrored { W - 1 - col } else { col }
+    }
+
+    /// Looks up the best column and the score, from the perspective of the
+    /// side to move, of the position.
+    pub(crate) fn get(
+        &mut self,
+        gameboard: &Gameboard<W, H>,
+        side_to_move: Player,
+        remaining_depth: usize,
+    ) -> Option<(Option<usize>, i32)> {
+        let result = self.peek(gameboard, side_to_move, remaining_depth)?;
+        self.record_hit();
+        Some(result)
+    }
+
+    /// Like [`Self::get`] but doesn't count the hit, so that it works on
+    /// tables that are shared between parallel tasks.
+    pub(crate) fn peek(
+        &self,
+        gameboard: &Gameboard<W, H>,
+        side_to_move: Player,
+        remaining_depth: usize,
+    ) -> Option<(Option<usize>, i32)> {
+        let (key, mirrored) = self.key(gameboard, side_to_move, remaining_depth);
+        let entry = self.entries.get(&key)?;
+        let best_col = entry.best_col.map(|col| Self::orient(col, mirrored));
+        Some((best_col, entry.score))
+    }
+
+    /// Counts a hit in another table, see [`Self::peek`].
+    pub(crate) const fn record_hit(&mut self) {
+        self.hits += 1;
+    }
+
+    /// Stores the best column and the score, from the perspective of the side
+    /// to move, of the position.
+    pub(crate) fn insert(
+        &mut self,
+        gameboard: &Gameboard<W, H>,
+        side_to_move: Player,
+        remaining_depth: usize,
+        (best_col, score): (Option<usize>, i32),
+    ) {
+        let (key, mirrored) = self.key(gameboard, side_to_move, remaining_depth);
+        let best_col = best_col.map(|col| Self::orient(col, mirrored));
+        self.insert_entry(key, Entry { best_col, score });
+    }
+
+    /// Stores the entry. If the table is full, all entries with the smallest
+    /// remaining depth are dropped first, unless the new entry has an even
+    /// smaller remaining depth; then it isn't stored.
+    fn insert_entry(&mut self, key: Key<W, H>, entry: Entry) {
+        if self.entries.len() >= self.capacity && !self.entries.contains_key(&key) {
+            let Some(min_depth) = self.entries.keys().map(|(_, _, depth, _)| *depth).min() else {
+                // no capacity at all
+                return;
+            };
+            if key.2 < min_depth {
+                return;
+            }
+            self.entries
+                .retain(|(_, _, depth, _), _| *depth > min_depth);
+        }
+        self.entries.insert(key, entry);
+    }
+
+    /// Moves all entries and hits of `other` into `self`, as far as the
+    /// capacity of `self` permits.
+    pub(crate) fn merge(&mut self, mut other: Self) {
+        if self.entries.len() + other.entries.len() <= self.capacity {
+            self.entries.append(&mut other.entries);
+        } else {
+            for (key, entry) in other.entries {
+                self.insert_entry(key, entry);
+            }
+        }
+        self.hits += other.hits;
+    }
+}
+
+impl<const W: usize, const H: usize> Default for TranspositionTable<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}